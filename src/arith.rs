@@ -0,0 +1,604 @@
+use crate::BitVec;
+use crate::primitive::Word;
+use crate::slice::{load_bits, store_bits};
+use alloc::vec;
+use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+use core::cmp::Ordering;
+use core::iter;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Range, Sub, SubAssign};
+
+/// Arithmetic treating the vector as an unsigned big-endian integer: the first
+/// bit is the most significant and the last bit is the least significant. The
+/// integer is split into word-sized limbs from its least significant end, and
+/// carries and borrows propagate from limb to limb.
+///
+/// The `overflowing_*` methods are fixed-width: they keep the length of the
+/// vector, wrap around and report whether the result fit. The `widening_*`
+/// methods are growing: they return a vector long enough to hold any result.
+/// The `+`, `-` and `*` operators and their assigning forms are fixed-width
+/// too, wrapping around within the length of the longer operand, so `a += &b`
+/// and `a = &a + &b` agree.
+impl<A: Allocator> BitVec<A> {
+    /// Adds one to the vector in place, wrapping around within its length.
+    /// Returns `true` if the addition overflowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![false, true, true];
+    /// assert!(!vec.increment());
+    /// assert_eq!(vec, bitvec![true, false, false]);
+    ///
+    /// let mut vec = bitvec![true, true, true];
+    /// assert!(vec.increment());
+    /// assert_eq!(vec, bitvec![false, false, false]);
+    /// ```
+    pub fn increment(&mut self) -> bool {
+        for index in 0..limb_count(self.len) {
+            let range = limb_range(self.len, index);
+            let max = usize::MAX >> (Word::BITS - range.len());
+            let value = unsafe { load_bits(&self.buf, range.clone()) } as usize;
+            if value != max {
+                unsafe { store_bits(&mut self.buf, range, value as u128 + 1) };
+                return false;
+            }
+            unsafe { store_bits(&mut self.buf, range, 0) };
+        }
+        true
+    }
+
+    /// Subtracts one from the vector in place, wrapping around within its
+    /// length. Returns `true` if the subtraction overflowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, false, false];
+    /// assert!(!vec.decrement());
+    /// assert_eq!(vec, bitvec![false, true, true]);
+    ///
+    /// let mut vec = bitvec![false, false, false];
+    /// assert!(vec.decrement());
+    /// assert_eq!(vec, bitvec![true, true, true]);
+    /// ```
+    pub fn decrement(&mut self) -> bool {
+        for index in 0..limb_count(self.len) {
+            let range = limb_range(self.len, index);
+            let value = unsafe { load_bits(&self.buf, range.clone()) };
+            if value != 0 {
+                unsafe { store_bits(&mut self.buf, range, value - 1) };
+                return false;
+            }
+            unsafe { store_bits(&mut self.buf, range, u128::MAX) };
+        }
+        true
+    }
+
+    /// Adds `rhs` to the vector in place, wrapping around within the length of
+    /// the vector. Returns `true` if the sum does not fit.
+    ///
+    /// The operands may differ in length; both are aligned at their least
+    /// significant bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![false, true, true];
+    /// assert!(!vec.overflowing_add(&bitvec![true, true]));
+    /// assert_eq!(vec, bitvec![true, true, false]);
+    /// assert!(vec.overflowing_add(&bitvec![true, true]));
+    /// assert_eq!(vec, bitvec![false, false, true]);
+    /// ```
+    pub fn overflowing_add<B: Allocator>(&mut self, rhs: &BitVec<B>) -> bool {
+        let mut carry = false;
+        for index in 0..limb_count(self.len) {
+            let range = limb_range(self.len, index);
+            let width = range.len();
+            let lhs = unsafe { load_bits(&self.buf, range.clone()) } as usize;
+            let rhs = unsafe { rhs.limb(index) };
+            let (sum, overflow) = lhs.overflowing_add(rhs);
+            let (sum, carry_overflow) = sum.overflowing_add(carry as usize);
+            carry = overflow || carry_overflow || (width < Word::BITS && sum >> width != 0);
+            unsafe { store_bits(&mut self.buf, range, sum as u128) };
+        }
+        carry || rhs.has_limbs_from(limb_count(self.len))
+    }
+
+    /// Subtracts `rhs` from the vector in place, wrapping around within the
+    /// length of the vector. Returns `true` if the difference is negative or
+    /// does not fit.
+    ///
+    /// The operands may differ in length; both are aligned at their least
+    /// significant bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, false, true];
+    /// assert!(!vec.overflowing_sub(&bitvec![true, true]));
+    /// assert_eq!(vec, bitvec![false, true, false]);
+    /// assert!(vec.overflowing_sub(&bitvec![true, true]));
+    /// assert_eq!(vec, bitvec![true, true, true]);
+    /// ```
+    pub fn overflowing_sub<B: Allocator>(&mut self, rhs: &BitVec<B>) -> bool {
+        let mut borrow = false;
+        for index in 0..limb_count(self.len) {
+            let range = limb_range(self.len, index);
+            let lhs = unsafe { load_bits(&self.buf, range.clone()) } as usize;
+            let rhs = unsafe { rhs.limb(index) };
+            let (diff, overflow) = lhs.overflowing_sub(rhs);
+            let (diff, borrow_overflow) = diff.overflowing_sub(borrow as usize);
+            borrow = overflow || borrow_overflow;
+            unsafe { store_bits(&mut self.buf, range, diff as u128) };
+        }
+        borrow || rhs.has_limbs_from(limb_count(self.len))
+    }
+
+    /// Multiplies the vector by `rhs` in place, wrapping around within the
+    /// length of the vector. Returns `true` if the product does not fit.
+    ///
+    /// The operands may differ in length; both are aligned at their least
+    /// significant bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![false, false, true, true];
+    /// assert!(!vec.overflowing_mul(&bitvec![true, false, true]));
+    /// assert_eq!(vec, bitvec![true, true, true, true]);
+    /// assert!(vec.overflowing_mul(&bitvec![true, true]));
+    /// assert_eq!(vec, bitvec![true, true, false, true]);
+    /// ```
+    pub fn overflowing_mul<B: Allocator>(&mut self, rhs: &BitVec<B>) -> bool {
+        let limbs = mul_limbs(&self.limbs(), &rhs.limbs());
+        let count = limb_count(self.len);
+        let mut overflow = limbs[count..].iter().any(|&limb| limb != 0);
+        for (index, &limb) in limbs[..count].iter().enumerate() {
+            let range = limb_range(self.len, index);
+            let width = range.len();
+            overflow |= width < Word::BITS && limb >> width != 0;
+            unsafe { store_bits(&mut self.buf, range, limb as u128) };
+        }
+        overflow
+    }
+
+    /// Returns the sum of the vector and `rhs` as a new [`BitVec`] one bit
+    /// longer than the longer operand, so that the sum never overflows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the sum exceeds `usize::MAX` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, true];
+    /// let sum = vec.widening_add(&bitvec![true]);
+    /// assert_eq!(sum, bitvec![true, false, false, false]);
+    /// ```
+    pub fn widening_add<B: Allocator>(&self, rhs: &BitVec<B>) -> BitVec {
+        let len = self
+            .len
+            .max(rhs.len)
+            .checked_add(1)
+            .expect("capacity overflow");
+        let mut sum = self.zero_extended(len);
+        sum.overflowing_add(rhs);
+        sum
+    }
+
+    /// Returns the product of the vector and `rhs` as a new [`BitVec`] whose
+    /// length is the sum of the operand lengths, so that the product never
+    /// overflows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the product exceeds `usize::MAX` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true];
+    /// let product = vec.widening_mul(&bitvec![true, false, true]);
+    /// assert_eq!(product, bitvec![false, true, true, true, true]);
+    /// ```
+    pub fn widening_mul<B: Allocator>(&self, rhs: &BitVec<B>) -> BitVec {
+        let len = self.len.checked_add(rhs.len).expect("capacity overflow");
+        let limbs = mul_limbs(&self.limbs(), &rhs.limbs());
+        let mut product = BitVec::zeroed(len);
+        for (index, &limb) in limbs.iter().enumerate().take(limb_count(len)) {
+            let range = limb_range(len, index);
+            unsafe { store_bits(&mut product.buf, range, limb as u128) };
+        }
+        product
+    }
+
+    /// Compares the vector and `other` as unsigned integers. Unlike [`Ord`],
+    /// leading clear bits do not affect the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    /// use std::cmp::Ordering;
+    ///
+    /// let vec = bitvec![false, false, true, false];
+    /// assert_eq!(vec.cmp_unsigned(&bitvec![true, false]), Ordering::Equal);
+    /// assert_eq!(vec.cmp_unsigned(&bitvec![true, true]), Ordering::Less);
+    /// assert_eq!(vec.cmp_unsigned(&bitvec![true]), Ordering::Greater);
+    /// ```
+    pub fn cmp_unsigned<B: Allocator>(&self, other: &BitVec<B>) -> Ordering {
+        let count = limb_count(self.len).max(limb_count(other.len));
+        for index in (0..count).rev() {
+            let lhs = unsafe { self.limb(index) };
+            let rhs = unsafe { other.limb(index) };
+            match lhs.cmp(&rhs) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Returns the limb at `index`, counting from the least significant end, or
+    /// zero if the vector has no such limb.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must hold at least `self.len` bits.
+    unsafe fn limb(&self, index: usize) -> usize {
+        if index >= limb_count(self.len) {
+            return 0;
+        }
+        let range = limb_range(self.len, index);
+        unsafe { load_bits(&self.buf, range) as usize }
+    }
+
+    /// Returns the vector zero-extended at its most significant end to `len`
+    /// bits, which must be at least its length.
+    fn zero_extended(&self, len: usize) -> BitVec {
+        let mut vec = BitVec::zeroed(len);
+        for index in 0..limb_count(self.len) {
+            let range = limb_range(len, index);
+            let value = unsafe { self.limb(index) } as u128;
+            unsafe { store_bits(&mut vec.buf, range, value) };
+        }
+        vec
+    }
+
+    /// Zero-extends the vector in place at its most significant end to `len`
+    /// bits, if it is shorter.
+    fn zero_extend(&mut self, len: usize) {
+        let Some(extra) = len.checked_sub(self.len) else {
+            return;
+        };
+        let count = self.len;
+        self.extend(iter::repeat_n(false, extra));
+        self.copy_within(..count, extra);
+        self.fill_range(..extra, false);
+    }
+
+    fn limbs(&self) -> Vec<usize> {
+        let count = limb_count(self.len);
+        (0..count)
+            .map(|index| unsafe { self.limb(index) })
+            .collect()
+    }
+
+    fn has_limbs_from(&self, index: usize) -> bool {
+        (index..limb_count(self.len)).any(|index| unsafe { self.limb(index) } != 0)
+    }
+}
+
+/// Multiplies two integers given as limbs from the least significant end,
+/// returning `lhs.len() + rhs.len()` limbs.
+fn mul_limbs(lhs: &[usize], rhs: &[usize]) -> Vec<usize> {
+    let mut limbs = vec![0; lhs.len() + rhs.len()];
+    for (i, &left) in lhs.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &right) in rhs.iter().enumerate() {
+            let value = left as u128 * right as u128 + limbs[i + j] as u128 + carry;
+            limbs[i + j] = value as usize;
+            carry = value >> Word::BITS;
+        }
+        limbs[i + rhs.len()] = carry as usize;
+    }
+    limbs
+}
+
+macro_rules! impl_arith_ops {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident) => {
+        impl<A: Allocator, B: Allocator> $op<BitVec<B>> for BitVec<A> {
+            type Output = BitVec<A>;
+
+            #[inline]
+            fn $method(self, rhs: BitVec<B>) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<A: Allocator, B: Allocator> $op<&BitVec<B>> for BitVec<A> {
+            type Output = BitVec<A>;
+
+            #[inline]
+            fn $method(mut self, rhs: &BitVec<B>) -> Self::Output {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+
+        impl<A: Allocator + Clone, B: Allocator> $op<BitVec<B>> for &BitVec<A> {
+            type Output = BitVec<A>;
+
+            #[inline]
+            fn $method(self, rhs: BitVec<B>) -> Self::Output {
+                self.clone().$method(&rhs)
+            }
+        }
+
+        impl<A: Allocator + Clone, B: Allocator> $op<&BitVec<B>> for &BitVec<A> {
+            type Output = BitVec<A>;
+
+            #[inline]
+            fn $method(self, rhs: &BitVec<B>) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<A: Allocator, B: Allocator> $assign_op<BitVec<B>> for BitVec<A> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: BitVec<B>) {
+                self.$assign_method(&rhs);
+            }
+        }
+    };
+}
+
+impl<A: Allocator, B: Allocator> AddAssign<&BitVec<B>> for BitVec<A> {
+    /// Performs the `+=` operation, wrapping around within the length of the
+    /// longer operand.
+    #[inline]
+    fn add_assign(&mut self, rhs: &BitVec<B>) {
+        self.zero_extend(rhs.len);
+        self.overflowing_add(rhs);
+    }
+}
+
+impl<A: Allocator, B: Allocator> SubAssign<&BitVec<B>> for BitVec<A> {
+    /// Performs the `-=` operation, wrapping around within the length of the
+    /// longer operand.
+    #[inline]
+    fn sub_assign(&mut self, rhs: &BitVec<B>) {
+        self.zero_extend(rhs.len);
+        self.overflowing_sub(rhs);
+    }
+}
+
+impl<A: Allocator, B: Allocator> MulAssign<&BitVec<B>> for BitVec<A> {
+    /// Performs the `*=` operation, wrapping around within the length of the
+    /// longer operand.
+    #[inline]
+    fn mul_assign(&mut self, rhs: &BitVec<B>) {
+        self.zero_extend(rhs.len);
+        self.overflowing_mul(rhs);
+    }
+}
+
+impl_arith_ops!(Add, add, AddAssign, add_assign);
+impl_arith_ops!(Sub, sub, SubAssign, sub_assign);
+impl_arith_ops!(Mul, mul, MulAssign, mul_assign);
+
+fn limb_count(len: usize) -> usize {
+    len.div_ceil(Word::BITS)
+}
+
+/// Returns the bit range of the limb at `index`, counting from the least
+/// significant end. The most significant limb may be shorter than a word.
+fn limb_range(len: usize, index: usize) -> Range<usize> {
+    let end = len - index * Word::BITS;
+    let start = end.saturating_sub(Word::BITS);
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+
+    fn values() -> impl Iterator<Item = u128> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u128;
+        (0..64).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
+
+    fn mask(width: usize) -> u128 {
+        u128::MAX >> (128 - width)
+    }
+
+    #[test]
+    fn test_increment() {
+        for width in [1, 7, Word::BITS, Word::BITS + 1, 127] {
+            for value in values()
+                .map(|value| value & mask(width))
+                .chain([0, mask(width)])
+            {
+                let mut vec = BitVec::from_u128_be(value, width);
+                vec.push_unused_word();
+                let overflow = vec.increment();
+                assert_eq!(overflow, value == mask(width));
+                assert_eq!(vec.load::<u128>(..), value.wrapping_add(1) & mask(width));
+                assert!(!vec.decrement() || overflow);
+                assert_eq!(vec.load::<u128>(..), value);
+            }
+        }
+
+        let mut vec = bitvec![true; Word::BITS * 3];
+        assert!(vec.increment());
+        assert_eq!(vec, bitvec![false; Word::BITS * 3]);
+        assert!(vec.decrement());
+        assert_eq!(vec, bitvec![true; Word::BITS * 3]);
+
+        assert!(BitVec::new().increment());
+        assert!(BitVec::new().decrement());
+    }
+
+    #[test]
+    fn test_overflowing_add_sub() {
+        for (lhs_width, rhs_width) in [(8, 8), (100, 3), (3, 100), (Word::BITS + 1, Word::BITS)] {
+            for (lhs, rhs) in values().zip(values().skip(1)) {
+                let lhs = lhs & mask(lhs_width);
+                let rhs = rhs & mask(rhs_width);
+
+                let mut vec = BitVec::from_u128_be(lhs, lhs_width);
+                let other = BitVec::from_u128_be(rhs, rhs_width);
+                let sum = lhs.checked_add(rhs).filter(|sum| *sum <= mask(lhs_width));
+                assert_eq!(vec.overflowing_add(&other), sum.is_none());
+                assert_eq!(
+                    vec.load::<u128>(..),
+                    lhs.wrapping_add(rhs) & mask(lhs_width)
+                );
+
+                let mut vec = BitVec::from_u128_be(lhs, lhs_width);
+                assert_eq!(vec.overflowing_sub(&other), lhs < rhs);
+                assert_eq!(
+                    vec.load::<u128>(..),
+                    lhs.wrapping_sub(rhs) & mask(lhs_width)
+                );
+
+                let vec = BitVec::from_u128_be(lhs, lhs_width);
+                let sum = vec.widening_add(&other);
+                assert_eq!(sum.len(), lhs_width.max(rhs_width) + 1);
+                assert_eq!(sum.load::<u128>(..), lhs + rhs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_widening_mul() {
+        for (lhs_width, rhs_width) in [(8, 8), (100, 20), (Word::BITS, Word::BITS)] {
+            for (lhs, rhs) in values().zip(values().skip(1)) {
+                let lhs = lhs & mask(lhs_width);
+                let rhs = rhs & mask(rhs_width);
+                let vec = BitVec::from_u128_be(lhs, lhs_width);
+                let product = vec.widening_mul(&BitVec::from_u128_be(rhs, rhs_width));
+                assert_eq!(product.len(), lhs_width + rhs_width);
+                assert_eq!(product.load::<u128>(..), lhs * rhs);
+            }
+        }
+
+        let vec = bitvec![true; Word::BITS * 2 + 3];
+        let mut expected = BitVec::zeroed(vec.len() + 3);
+        for _ in 0..5 {
+            assert!(!expected.overflowing_add(&vec));
+        }
+        assert_eq!(vec.widening_mul(&bitvec![true, false, true]), expected);
+        assert_eq!(vec.widening_mul(&BitVec::new()), BitVec::zeroed(vec.len()));
+    }
+
+    #[test]
+    fn test_overflowing_mul() {
+        for (lhs_width, rhs_width) in [(8, 8), (100, 20), (20, 100), (Word::BITS + 1, 3)] {
+            for (lhs, rhs) in values().zip(values().skip(1)) {
+                let lhs = lhs & mask(lhs_width);
+                let rhs = rhs & mask(rhs_width);
+                let mut vec = BitVec::from_u128_be(lhs, lhs_width);
+                vec.push_unused_word();
+                let other = BitVec::from_u128_be(rhs, rhs_width);
+                let product = lhs * rhs;
+                assert_eq!(vec.overflowing_mul(&other), product > mask(lhs_width));
+                assert_eq!(vec.load::<u128>(..), product & mask(lhs_width));
+            }
+        }
+
+        let mut vec = bitvec![true; Word::BITS * 2 + 3];
+        assert!(vec.overflowing_mul(&bitvec![true, false]));
+        let mut expected = bitvec![true; Word::BITS * 2 + 2];
+        expected.push(false);
+        assert_eq!(vec, expected);
+        assert!(!vec.overflowing_mul(&bitvec![true]));
+        assert!(!vec.overflowing_mul(&BitVec::new()));
+        assert_eq!(vec, bitvec![false; Word::BITS * 2 + 3]);
+        assert!(!BitVec::new().overflowing_mul(&bitvec![true; 3]));
+    }
+
+    #[test]
+    fn test_operators() {
+        let lhs = BitVec::from_u128_be(200, 8);
+        let rhs = BitVec::from_u128_be(100, 7);
+
+        let sum = &lhs + &rhs;
+        assert_eq!((sum.len(), sum.load::<u8>(..)), (8, 44));
+        assert_eq!(lhs.clone() + rhs.clone(), sum);
+        assert_eq!(lhs.clone() + &rhs, sum);
+        assert_eq!(&lhs + rhs.clone(), sum);
+        assert_eq!(&rhs + &lhs, sum);
+
+        let diff = &lhs - &rhs;
+        assert_eq!((diff.len(), diff.load::<u8>(..)), (8, 100));
+        let diff = &rhs - &lhs;
+        assert_eq!((diff.len(), diff.load::<u8>(..)), (8, 156));
+        assert_eq!(rhs.clone() - lhs.clone(), diff);
+
+        let product = &lhs * &rhs;
+        assert_eq!((product.len(), product.load::<u8>(..)), (8, 32));
+        assert_eq!(lhs.clone() * rhs.clone(), product);
+        assert_eq!(&rhs * &lhs, product);
+
+        let mut vec = lhs.clone();
+        vec += &rhs;
+        assert_eq!(vec, sum);
+        vec -= rhs.clone();
+        assert_eq!(vec, lhs);
+        vec *= &rhs;
+        assert_eq!(vec, product);
+
+        // The assigning forms extend a shorter left operand like the others.
+        let mut vec = rhs.clone();
+        vec += &lhs;
+        assert_eq!(vec, sum);
+        let mut vec = rhs.clone();
+        vec -= &lhs;
+        assert_eq!(vec, diff);
+        let mut vec = BitVec::from_u128_be(3, 2);
+        vec *= &BitVec::from_u128_be(u128::MAX, Word::BITS + 3);
+        let expected = u128::MAX.wrapping_mul(3) & mask(Word::BITS + 3);
+        assert_eq!(vec.load::<u128>(..), expected);
+    }
+
+    #[test]
+    fn test_cmp_unsigned() {
+        for (lhs, rhs) in values().zip(values().skip(1)) {
+            let vec = BitVec::from_u128_be(lhs >> 3, 125);
+            let other = BitVec::from_u128_be(rhs >> 60, 100);
+            assert_eq!(vec.cmp_unsigned(&other), (lhs >> 3).cmp(&(rhs >> 60)));
+            assert_eq!(vec.cmp_unsigned(&vec.clone()), Ordering::Equal);
+        }
+
+        let mut vec = bitvec![false; Word::BITS * 2];
+        vec.push(true);
+        assert_eq!(vec.cmp_unsigned(&bitvec![true]), Ordering::Equal);
+        assert_eq!(
+            BitVec::new().cmp_unsigned(&bitvec![false; 3]),
+            Ordering::Equal
+        );
+    }
+}
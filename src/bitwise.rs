@@ -1,58 +1,55 @@
 use crate::BitVec;
 use crate::primitive::Word;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
 
 mod and;
 mod not;
 mod or;
+mod reduce;
 mod xor;
 
-impl BitVec {
+impl<A: Allocator + Clone> BitVec<A> {
     fn bitwise_operation<F>(&self, rhs: &Self, op: F) -> Self
     where
         F: FnMut((&Word, &Word)) -> Word,
     {
         let len = self.len.min(rhs.len);
         let buf_len = len.div_ceil(Word::BITS);
-        let buf = self
-            .buf
-            .iter()
-            .zip(&rhs.buf)
-            .map(op)
-            .take(buf_len)
-            .collect();
+        let alloc = self.buf.allocator().clone();
+        let mut buf = Vec::with_capacity_in(buf_len, alloc);
+        buf.extend(self.buf.iter().zip(rhs.buf.iter()).map(op).take(buf_len));
         Self { len, buf }
     }
+}
 
-    fn bitwise_operation_consume_self<F>(self, rhs: &Self, op: F) -> Self
+impl<A: Allocator> BitVec<A> {
+    fn bitwise_operation_consume_self<F>(mut self, rhs: &Self, mut op: F) -> Self
     where
         F: FnMut((Word, &Word)) -> Word,
     {
         let len = self.len.min(rhs.len);
         let buf_len = len.div_ceil(Word::BITS);
-        let buf = self
-            .buf
-            .into_iter()
-            .zip(&rhs.buf)
-            .map(op)
-            .take(buf_len)
-            .collect();
-        Self { len, buf }
+        self.buf.truncate(buf_len);
+        for (left, right) in self.buf.iter_mut().zip(rhs.buf.iter()) {
+            *left = op((*left, right));
+        }
+        self.len = len;
+        self
     }
 
-    fn bitwise_operation_consume_both<F>(self, rhs: Self, op: F) -> Self
+    fn bitwise_operation_consume_both<F>(mut self, rhs: Self, mut op: F) -> Self
     where
         F: FnMut((Word, Word)) -> Word,
     {
         let len = self.len.min(rhs.len);
         let buf_len = len.div_ceil(Word::BITS);
-        let buf = self
-            .buf
-            .into_iter()
-            .zip(rhs.buf)
-            .map(op)
-            .take(buf_len)
-            .collect();
-        Self { len, buf }
+        self.buf.truncate(buf_len);
+        for (left, right) in self.buf.iter_mut().zip(rhs.buf) {
+            *left = op((*left, right));
+        }
+        self.len = len;
+        self
     }
 }
 
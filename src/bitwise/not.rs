@@ -1,8 +1,10 @@
 use crate::BitVec;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
 use core::ops::Not;
 
-impl Not for BitVec {
-    type Output = BitVec;
+impl<A: Allocator> Not for BitVec<A> {
+    type Output = BitVec<A>;
 
     #[inline]
     fn not(mut self) -> Self::Output {
@@ -15,14 +17,16 @@ impl Not for BitVec {
     }
 }
 
-impl Not for &BitVec {
-    type Output = BitVec;
+impl<A: Allocator + Clone> Not for &BitVec<A> {
+    type Output = BitVec<A>;
 
     #[inline]
     fn not(self) -> Self::Output {
         let len = self.len;
         let buf_len = self.buf_used();
-        let buf = self.buf.iter().map(|word| !*word).take(buf_len).collect();
+        let alloc = self.buf.allocator().clone();
+        let mut buf = Vec::with_capacity_in(buf_len, alloc);
+        buf.extend(self.buf.iter().map(|word| !*word).take(buf_len));
         BitVec { len, buf }
     }
 }
@@ -1,46 +1,47 @@
 use crate::BitVec;
+use allocator_api2::alloc::Allocator;
 use core::ops::BitOr;
 
-impl BitOr for BitVec {
-    type Output = BitVec;
+impl<A: Allocator> BitOr for BitVec<A> {
+    type Output = BitVec<A>;
 
     /// Performs the `|` operation, returning a new `BitVec` with the length of the
     /// shorter input.
     #[inline]
-    fn bitor(self, rhs: BitVec) -> Self::Output {
+    fn bitor(self, rhs: BitVec<A>) -> Self::Output {
         self.bitwise_operation_consume_both(rhs, |(left, right)| left | right)
     }
 }
 
-impl BitOr<&BitVec> for BitVec {
-    type Output = BitVec;
+impl<A: Allocator> BitOr<&BitVec<A>> for BitVec<A> {
+    type Output = BitVec<A>;
 
     /// Performs the `|` operation, returning a new `BitVec` with the length of the
     /// shorter input.
     #[inline]
-    fn bitor(self, rhs: &BitVec) -> Self::Output {
+    fn bitor(self, rhs: &BitVec<A>) -> Self::Output {
         self.bitwise_operation_consume_self(rhs, |(left, right)| left | *right)
     }
 }
 
-impl BitOr<BitVec> for &BitVec {
-    type Output = BitVec;
+impl<A: Allocator> BitOr<BitVec<A>> for &BitVec<A> {
+    type Output = BitVec<A>;
 
     /// Performs the `|` operation, returning a new `BitVec` with the length of the
     /// shorter input.
     #[inline]
-    fn bitor(self, rhs: BitVec) -> Self::Output {
+    fn bitor(self, rhs: BitVec<A>) -> Self::Output {
         rhs | self
     }
 }
 
-impl BitOr for &BitVec {
-    type Output = BitVec;
+impl<A: Allocator + Clone> BitOr for &BitVec<A> {
+    type Output = BitVec<A>;
 
     /// Performs the `|` operation, returning a new `BitVec` with the length of the
     /// shorter input.
     #[inline]
-    fn bitor(self, rhs: &BitVec) -> Self::Output {
+    fn bitor(self, rhs: &BitVec<A>) -> Self::Output {
         self.bitwise_operation(rhs, |(left, right)| *left | *right)
     }
 }
@@ -0,0 +1,257 @@
+use crate::BitVec;
+use crate::primitive::Word;
+use alloc::vec;
+use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+
+impl BitVec {
+    /// Performs the `&` operation across all vectors at once, returning a new
+    /// [`BitVec`] with the length of the shortest input. Returns an empty vector
+    /// if there are no inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vecs = [
+    ///     bitvec![true, true, true, false],
+    ///     bitvec![true, true, false, false],
+    ///     bitvec![true, false, true],
+    /// ];
+    /// assert_eq!(BitVec::and_all(&vecs), bitvec![true, false, false]);
+    /// ```
+    pub fn and_all<'a, I, B>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a BitVec<B>>,
+        B: Allocator + 'a,
+    {
+        reduce(iter, |words| {
+            words.fold(!Word::CLEAR, |acc, word| acc & word)
+        })
+    }
+
+    /// Performs the `|` operation across all vectors at once, returning a new
+    /// [`BitVec`] with the length of the shortest input. Returns an empty vector
+    /// if there are no inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vecs = [
+    ///     bitvec![false, false, true, false],
+    ///     bitvec![true, false, false, false],
+    ///     bitvec![false, false, false],
+    /// ];
+    /// assert_eq!(BitVec::or_all(&vecs), bitvec![true, false, true]);
+    /// ```
+    pub fn or_all<'a, I, B>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a BitVec<B>>,
+        B: Allocator + 'a,
+    {
+        reduce(iter, |words| {
+            words.fold(Word::CLEAR, |acc, word| acc | word)
+        })
+    }
+
+    /// Performs the `^` operation across all vectors at once, returning a new
+    /// [`BitVec`] with the length of the shortest input. Returns an empty vector
+    /// if there are no inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vecs = [
+    ///     bitvec![true, true, true, false],
+    ///     bitvec![true, true, false, false],
+    ///     bitvec![true, false, false],
+    /// ];
+    /// assert_eq!(BitVec::xor_all(&vecs), bitvec![true, false, true]);
+    /// ```
+    pub fn xor_all<'a, I, B>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a BitVec<B>>,
+        B: Allocator + 'a,
+    {
+        reduce(iter, |words| {
+            words.fold(Word::CLEAR, |acc, word| acc ^ word)
+        })
+    }
+
+    /// Returns a new [`BitVec`] in which each bit is set if it is set in more
+    /// than half of the inputs. The result has the length of the shortest input,
+    /// and is empty if there are no inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vecs = [
+    ///     bitvec![true, true, true, false],
+    ///     bitvec![true, true, false, false],
+    ///     bitvec![true, false, false],
+    /// ];
+    /// assert_eq!(BitVec::majority(&vecs), bitvec![true, true, false]);
+    /// ```
+    pub fn majority<'a, I, B>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a BitVec<B>>,
+        B: Allocator + 'a,
+    {
+        let vecs: Vec<_> = iter.into_iter().collect();
+        let threshold = vecs.len() / 2 + 1;
+        Self::threshold(vecs, threshold)
+    }
+
+    /// Returns a new [`BitVec`] in which each bit is set if it is set in at
+    /// least `k` of the inputs. The result has the length of the shortest input,
+    /// and is empty if there are no inputs.
+    ///
+    /// The set bits of each word column are counted with bit-sliced counters, so
+    /// that a whole word of positions is tallied at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vecs = [
+    ///     bitvec![true, true, true, false],
+    ///     bitvec![true, true, false, false],
+    ///     bitvec![true, false, false, true],
+    /// ];
+    /// assert_eq!(BitVec::threshold(&vecs, 1), bitvec![true, true, true, true]);
+    /// assert_eq!(BitVec::threshold(&vecs, 3), bitvec![true, false, false, false]);
+    /// ```
+    pub fn threshold<'a, I, B>(iter: I, k: usize) -> Self
+    where
+        I: IntoIterator<Item = &'a BitVec<B>>,
+        B: Allocator + 'a,
+    {
+        let vecs: Vec<_> = iter.into_iter().collect();
+        let planes = (usize::BITS - vecs.len().max(k).leading_zeros()) as usize;
+        let mut counter = vec![Word::CLEAR; planes];
+        reduce(vecs, |words| {
+            counter.fill(Word::CLEAR);
+            for word in words {
+                // Ripple-carry add the word into the counter.
+                let mut carry = word;
+                for plane in counter.iter_mut() {
+                    let sum = *plane ^ carry;
+                    carry = *plane & carry;
+                    *plane = sum;
+                }
+            }
+
+            // Compare the counter with `k`, from the most significant plane.
+            let mut greater = Word::CLEAR;
+            let mut equal = !Word::CLEAR;
+            for (index, &plane) in counter.iter().enumerate().rev() {
+                if k >> index & 1 == 1 {
+                    equal &= plane;
+                } else {
+                    greater |= equal & plane;
+                    equal &= !plane;
+                }
+            }
+            greater | equal
+        })
+    }
+}
+
+/// Collects the inputs, then builds each word of the result by passing `op` the
+/// words of that column from every input.
+fn reduce<'a, I, B, F>(iter: I, mut op: F) -> BitVec
+where
+    I: IntoIterator<Item = &'a BitVec<B>>,
+    B: Allocator + 'a,
+    F: FnMut(&mut dyn Iterator<Item = Word>) -> Word,
+{
+    let vecs: Vec<&BitVec<B>> = iter.into_iter().collect();
+    let len = vecs.iter().map(|vec| vec.len).min().unwrap_or(0);
+    let buf_len = len.div_ceil(Word::BITS);
+    let mut vec = BitVec::with_capacity(len);
+    for period in 0..buf_len {
+        let mut words = vecs
+            .iter()
+            .map(|vec| unsafe { *vec.buf.get_unchecked(period) });
+        vec.buf.push(op(&mut words));
+    }
+    vec.len = len;
+    vec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Bit;
+
+    const LEN: usize = Word::BITS * 2 + 3;
+
+    fn inputs() -> Vec<BitVec> {
+        (0..7)
+            .map(|seed| {
+                let len = LEN + seed % 3;
+                let mut vec: BitVec = (0..len)
+                    .map(|index| (index * 7 + seed * 3) % (seed + 2) == 0)
+                    .collect();
+                vec.push(true).pop();
+                vec.push_unused_word();
+                vec
+            })
+            .collect()
+    }
+
+    fn naive(vecs: &[BitVec], op: impl Fn(&[Bit]) -> Bit) -> BitVec {
+        let len = vecs.iter().map(BitVec::len).min().unwrap_or(0);
+        (0..len)
+            .map(|index| {
+                let column: Vec<Bit> = vecs.iter().map(|vec| vec[index]).collect();
+                op(&column)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reduce() {
+        let vecs = inputs();
+        assert_eq!(
+            BitVec::and_all(&vecs),
+            naive(&vecs, |bits| bits.iter().all(|bit| *bit))
+        );
+        assert_eq!(
+            BitVec::or_all(&vecs),
+            naive(&vecs, |bits| bits.iter().any(|bit| *bit))
+        );
+        assert_eq!(
+            BitVec::xor_all(&vecs),
+            naive(&vecs, |bits| bits.iter().filter(|bit| **bit).count() % 2
+                == 1)
+        );
+        assert_eq!(BitVec::and_all(&vecs[..1]), vecs[0]);
+        assert_eq!(BitVec::or_all(&[] as &[BitVec]), BitVec::new());
+    }
+
+    #[test]
+    fn test_threshold() {
+        let vecs = inputs();
+        for k in 0..=vecs.len() + 1 {
+            let expected = naive(&vecs, |bits| bits.iter().filter(|bit| **bit).count() >= k);
+            assert_eq!(BitVec::threshold(&vecs, k), expected);
+        }
+        for count in 1..=vecs.len() {
+            let vecs = &vecs[..count];
+            let expected = naive(vecs, |bits| {
+                bits.iter().filter(|bit| **bit).count() * 2 > bits.len()
+            });
+            assert_eq!(BitVec::majority(vecs), expected);
+        }
+        assert_eq!(BitVec::majority(&[] as &[BitVec]), BitVec::new());
+    }
+}
@@ -0,0 +1,934 @@
+use crate::primitive::{Bit, Word};
+use crate::{BitSlice, BitVec, Integer, IntoIter, Iter, Loc, Matches};
+use crate::{checked_range, eq_bits, field, hash_bits, range, resolve_range, search, slice};
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::boxed::Box;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, RangeBounds};
+
+mod bitwise;
+
+/// A fixed-length bit vector.
+///
+/// Unlike [`BitVec`], a [`BitBox`] neither keeps spare capacity nor any
+/// unused words, so it never takes more memory than its bits require.
+pub struct BitBox<A: Allocator = Global> {
+    // Invariant: `self.buf.len() == self.len.div_ceil(Word::BITS)`
+    len: usize,
+    buf: Box<[Word], A>,
+}
+
+impl<A: Allocator> BitBox<A> {
+    /// Returns the number of bits in the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert_eq!(boxed.len(), 4);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the box contains no bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![].into_boxed_bitslice();
+    /// assert!(boxed.is_empty());
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert!(!boxed.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at the specified index, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert_eq!(boxed.get(3), Some(false));
+    /// assert_eq!(boxed.get(4), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            None
+        } else {
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
+
+    /// Returns the bit at the specified index, without performing any bounds
+    /// checking.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with an out-of-bounds index is *[undefined behavior]*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert_eq!(unsafe { boxed.get_unchecked(3) }, false);
+    /// ```
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> Bit {
+        let loc = Loc::new(index);
+        let word = unsafe { self.buf.get_unchecked(loc.period) };
+        word.get(loc.offset)
+    }
+
+    /// Sets the bit at the specified index to the specified value, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert!(boxed.set(2, true).is_some());
+    /// assert!(boxed.set(3, true).is_some());
+    /// assert!(boxed.set(4, true).is_none());
+    /// assert_eq!(boxed, bitvec![true; 4].into_boxed_bitslice());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn set(&mut self, index: usize, value: Bit) -> Option<&mut Self> {
+        if index >= self.len {
+            None
+        } else {
+            Some(unsafe { self.set_unchecked(index, value) })
+        }
+    }
+
+    /// Sets the bit at the specified index to the specified value, without
+    /// performing any bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with an out-of-bounds index is *[undefined behavior]*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// unsafe {
+    ///     boxed.set_unchecked(2, true);
+    ///     boxed.set_unchecked(3, true);
+    /// }
+    /// assert_eq!(boxed, bitvec![true; 4].into_boxed_bitslice());
+    /// ```
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[inline]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: Bit) -> &mut Self {
+        let loc = Loc::new(index);
+        let word = unsafe { self.buf.get_unchecked_mut(loc.period) };
+        word.set(loc.offset, value);
+        self
+    }
+
+    /// Returns an iterator over the bits of the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// let mut iter = boxed.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(true));
+    /// assert_eq!(iter.next_back(), Some(false));
+    /// assert_eq!(iter.next(), Some(true));
+    /// assert_eq!(iter.next_back(), Some(false));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.buf, 0..self.len)
+    }
+
+    /// Converts the box into a [`BitVec`] without copying or reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// let mut vec = boxed.into_bitvec();
+    /// vec.push(true);
+    /// assert_eq!(vec, bitvec![true, true, false, false, true]);
+    /// ```
+    #[inline]
+    pub fn into_bitvec(self) -> BitVec<A> {
+        let len = self.len;
+        let buf = self.buf.into_vec();
+        BitVec { len, buf }
+    }
+}
+
+impl<A: Allocator> BitBox<A> {
+    /// Returns a [`BitSlice`] over the bits of the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert_eq!(boxed.as_bitslice(), bitvec![true, true, false, false]);
+    /// ```
+    #[inline]
+    pub fn as_bitslice(&self) -> BitSlice<'_> {
+        BitSlice::new(&self.buf, 0..self.len)
+    }
+
+    /// Returns the number of set bits in the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, true].into_boxed_bitslice();
+    /// assert_eq!(boxed.count_ones(), 3);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        unsafe { slice::count_ones(&self.buf, 0..self.len) }
+    }
+
+    /// Returns the number of unset bits in the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, false, true].into_boxed_bitslice();
+    /// assert_eq!(boxed.count_zeros(), 1);
+    /// ```
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Sets all bits of the box to the specified value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// boxed.fill(true);
+    /// assert_eq!(boxed, bitvec![true; 4].into_boxed_bitslice());
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: Bit) -> &mut Self {
+        self.fill_range(.., value)
+    }
+
+    /// Sets the bits in the specified range to the specified value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// boxed.fill_range(1..3, false);
+    /// assert_eq!(boxed, bitvec![true, false, false, false].into_boxed_bitslice());
+    /// ```
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::fill_range(&mut self.buf, range, value) };
+        self
+    }
+
+    /// Sets the bits in the specified range to the specified value, if in
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// assert!(boxed.set_range(2.., true).is_some());
+    /// assert!(boxed.set_range(3..5, false).is_none());
+    /// assert_eq!(boxed, bitvec![true; 4].into_boxed_bitslice());
+    /// ```
+    #[must_use]
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> Option<&mut Self> {
+        let range = checked_range(range, self.len)?;
+        unsafe { range::fill_range(&mut self.buf, range, value) };
+        Some(self)
+    }
+
+    /// Flips the bits in the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// boxed.toggle_range(1..3);
+    /// assert_eq!(boxed, bitvec![true, false, true, false].into_boxed_bitslice());
+    /// ```
+    pub fn toggle_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::toggle_range(&mut self.buf, range) };
+        self
+    }
+
+    /// Copies the bits in `src` to the position starting at `dest`. The two
+    /// ranges may overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds, or if the destination range ends past
+    /// the end of the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false, false].into_boxed_bitslice();
+    /// boxed.copy_within(0..3, 2);
+    /// assert_eq!(boxed, bitvec![true, true, true, true, false].into_boxed_bitslice());
+    /// ```
+    pub fn copy_within(&mut self, src: impl RangeBounds<usize>, dest: usize) -> &mut Self {
+        let src = resolve_range(src, self.len);
+        assert!(dest <= self.len - src.len(), "dest is out of bounds");
+        unsafe { range::copy_within(&mut self.buf, src, dest) };
+        self
+    }
+
+    /// Swaps two bits in the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+    /// boxed.swap(0, 3);
+    /// assert_eq!(boxed, bitvec![false, true, false, true].into_boxed_bitslice());
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) -> &mut Self {
+        assert!(a < self.len && b < self.len, "index out of bounds");
+        unsafe { range::swap(&mut self.buf, a, b) };
+        self
+    }
+
+    /// Reverses the order of bits in the box, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, true, false].into_boxed_bitslice();
+    /// boxed.reverse();
+    /// assert_eq!(boxed, bitvec![false, true, false, true, true].into_boxed_bitslice());
+    /// ```
+    #[inline]
+    pub fn reverse(&mut self) -> &mut Self {
+        unsafe { range::reverse(&mut self.buf, self.len) };
+        self
+    }
+
+    /// Reverses the order of bits in the specified range, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![true, true, false, true, false].into_boxed_bitslice();
+    /// boxed.reverse_range(..3);
+    /// assert_eq!(boxed, bitvec![false, true, true, true, false].into_boxed_bitslice());
+    /// ```
+    pub fn reverse_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::reverse_range(&mut self.buf, range) };
+        self
+    }
+
+    /// Loads the bits in `range` as an integer in big-endian order, which is
+    /// the same as [`load_be`](BitBox::load_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![0b10110011, 0b01010101].into_boxed_bitslice();
+    /// assert_eq!(boxed.load::<u8>(4..12), 0b00110101);
+    /// assert_eq!(boxed.load::<i8>(0..4), -5);
+    /// ```
+    #[inline]
+    pub fn load<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        self.load_be(range)
+    }
+
+    /// Loads the bits in `range` as an integer, with the first bit being the
+    /// most significant. See [`BitVec::load_be`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![0b10110011, 0b01010101].into_boxed_bitslice();
+    /// assert_eq!(boxed.load_be::<u16>(2..14), 0b110011_010101);
+    /// ```
+    pub fn load_be<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::load_be(&self.buf, range) }
+    }
+
+    /// Loads the bits in `range` as an integer in little-endian order. See
+    /// [`BitVec::load_le`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![0b10110011, 0b01010101].into_boxed_bitslice();
+    /// assert_eq!(boxed.load_le::<u16>(0..12), 0b0101_10110011);
+    /// ```
+    pub fn load_le<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::load_le(&self.buf, range) }
+    }
+
+    /// Stores the lowest bits of `value` into `range` in big-endian order, which
+    /// is the same as [`store_be`](BitBox::store_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![0b00000000, 0b00000000].into_boxed_bitslice();
+    /// boxed.store(4..12, 0xffu8);
+    /// assert_eq!(boxed, bitvec![0b00001111, 0b11110000].into_boxed_bitslice());
+    /// ```
+    #[inline]
+    pub fn store<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        self.store_be(range, value);
+    }
+
+    /// Stores the lowest bits of `value` into `range`, with the first bit being
+    /// the most significant. See [`BitVec::store_be`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![0b00000000, 0b00000000].into_boxed_bitslice();
+    /// boxed.store_be(2..14, -1i16);
+    /// assert_eq!(boxed, bitvec![0b00111111, 0b11111100].into_boxed_bitslice());
+    /// ```
+    pub fn store_be<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::store_be(&mut self.buf, range, value) };
+    }
+
+    /// Stores the lowest bits of `value` into `range` in little-endian order.
+    /// See [`BitVec::store_le`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut boxed = bitvec![0b00000000, 0b00000000].into_boxed_bitslice();
+    /// boxed.store_le(.., 0x1234u16);
+    /// assert_eq!(boxed, bitvec![0x34, 0x12].into_boxed_bitslice());
+    /// ```
+    pub fn store_le<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::store_le(&mut self.buf, range, value) };
+    }
+
+    /// Returns the index of the first occurrence of `pattern` in the box, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, false, true, true, false, true, true].into_boxed_bitslice();
+    /// assert_eq!(boxed.find(&bitvec![true, true]), Some(2));
+    /// assert_eq!(boxed.find(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn find<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { search::find(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns the index of the last occurrence of `pattern` in the box, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at the end of the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, false, true, true, false, true, true].into_boxed_bitslice();
+    /// assert_eq!(boxed.rfind(&bitvec![true, true]), Some(5));
+    /// assert_eq!(boxed.rfind(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn rfind<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { search::rfind(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns an iterator over the indices of the non-overlapping occurrences
+    /// of `pattern` in the box, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, true, false, true, true].into_boxed_bitslice();
+    /// let matches: Vec<usize> = boxed.find_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 4]);
+    /// ```
+    #[inline]
+    pub fn find_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(&self.buf, self.len, pattern, false) }
+    }
+
+    /// Returns an iterator over the indices of all occurrences of `pattern` in
+    /// the box, including overlapping ones, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, true, true, false, true, true].into_boxed_bitslice();
+    /// let matches: Vec<usize> = boxed.find_overlapping_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 1, 4]);
+    /// ```
+    #[inline]
+    pub fn find_overlapping_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(&self.buf, self.len, pattern, true) }
+    }
+
+    /// Returns `true` if `pattern` occurs anywhere in the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, false, true, true].into_boxed_bitslice();
+    /// assert!(boxed.contains_pattern(&bitvec![false, true]));
+    /// assert!(!boxed.contains_pattern(&bitvec![false, false]));
+    /// ```
+    #[inline]
+    pub fn contains_pattern<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        self.find(pattern).is_some()
+    }
+
+    /// Returns `true` if the box begins with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, false, true, true].into_boxed_bitslice();
+    /// assert!(boxed.starts_with(&bitvec![true, false]));
+    /// assert!(!boxed.starts_with(&bitvec![true, true]));
+    /// ```
+    #[inline]
+    pub fn starts_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { search::starts_with(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns `true` if the box ends with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let boxed = bitvec![true, false, true, true].into_boxed_bitslice();
+    /// assert!(boxed.ends_with(&bitvec![true, true]));
+    /// assert!(!boxed.ends_with(&bitvec![false, true]));
+    /// ```
+    #[inline]
+    pub fn ends_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { search::ends_with(&self.buf, self.len, pattern) }
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
+    /// Converts the vector into a [`BitBox`].
+    ///
+    /// Any excess capacity and unused words are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.reserve(100);
+    ///
+    /// let boxed = vec.into_boxed_bitslice();
+    /// assert_eq!(boxed.len(), 4);
+    /// ```
+    pub fn into_boxed_bitslice(mut self) -> BitBox<A> {
+        let len = self.len;
+        let buf_len = self.buf_used();
+        self.buf.truncate(buf_len);
+        let buf = self.buf.into_boxed_slice();
+        BitBox { len, buf }
+    }
+}
+
+impl<A: Allocator> From<BitVec<A>> for BitBox<A> {
+    #[inline]
+    fn from(value: BitVec<A>) -> Self {
+        value.into_boxed_bitslice()
+    }
+}
+
+impl<A: Allocator> From<BitBox<A>> for BitVec<A> {
+    #[inline]
+    fn from(value: BitBox<A>) -> Self {
+        value.into_bitvec()
+    }
+}
+
+impl FromIterator<Bit> for BitBox {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Bit>,
+    {
+        BitVec::from_iter(iter).into_boxed_bitslice()
+    }
+}
+
+impl Default for BitBox {
+    #[inline]
+    fn default() -> Self {
+        BitVec::new().into_boxed_bitslice()
+    }
+}
+
+impl<A: Allocator> IntoIterator for BitBox<A> {
+    type Item = Bit;
+    type IntoIter = IntoIter<A>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_bitvec().into_iter()
+    }
+}
+
+impl<'a, A: Allocator> IntoIterator for &'a BitBox<A> {
+    type Item = Bit;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<A: Allocator> Index<usize> for BitBox<A> {
+    type Output = Bit;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            None => panic!("index out of bounds"),
+            Some(false) => &false,
+            Some(true) => &true,
+        }
+    }
+}
+
+impl<A: Allocator + Clone> Clone for BitBox<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let len = self.len;
+        let buf = self.buf.clone();
+        Self { len, buf }
+    }
+}
+
+impl<A: Allocator> fmt::Debug for BitBox<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<A: Allocator> Hash for BitBox<A> {
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        unsafe { hash_bits(self.len, &self.buf, state) };
+    }
+}
+
+impl<A: Allocator, B: Allocator> PartialEq<BitBox<B>> for BitBox<A> {
+    #[inline]
+    fn eq(&self, other: &BitBox<B>) -> bool {
+        self.len == other.len && unsafe { eq_bits(self.len, &self.buf, &other.buf) }
+    }
+}
+
+impl<A: Allocator> Eq for BitBox<A> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::bitvec;
+    use std::hash::DefaultHasher;
+
+    #[test]
+    fn test_into_boxed_bitslice() {
+        let mut vec = bitvec![true; Word::BITS + 1];
+        vec.reserve(Word::BITS * 2);
+        vec.push_unused_word();
+
+        let boxed = vec.into_boxed_bitslice();
+        assert_eq!(boxed.len, Word::BITS + 1);
+        assert_eq!(boxed.buf.len(), 2);
+
+        let mut vec = bitvec![true; Word::BITS + 1];
+        vec.pop();
+
+        let boxed = vec.into_boxed_bitslice();
+        assert_eq!(boxed.len, Word::BITS);
+        assert_eq!(boxed.buf.len(), 1);
+    }
+
+    #[test]
+    fn test_into_bitvec() {
+        let vec = bitvec![true, true, false, false];
+        let unchanged = vec.clone();
+
+        let vec = vec.into_boxed_bitslice().into_bitvec();
+        assert_eq!(vec, unchanged);
+        assert_eq!(vec.buf.len(), 1);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut boxed = bitvec![true; Word::BITS + 1].into_boxed_bitslice();
+
+        assert_eq!(boxed.get(Word::BITS), Some(true));
+        assert_eq!(boxed.get(Word::BITS + 1), None);
+
+        assert!(boxed.set(Word::BITS, false).is_some());
+        assert!(boxed.set(Word::BITS + 1, false).is_none());
+        assert_eq!(boxed.get(Word::BITS), Some(false));
+        assert!(!boxed[Word::BITS]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let boxed = bitvec![true, true, false, false].into_boxed_bitslice();
+
+        let mut iter = boxed.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = boxed.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_count() {
+        let mut vec = bitvec![true; Word::BITS * 2];
+        vec.pop();
+        assert!(vec.set(3, false).is_some());
+        let boxed = vec.into_boxed_bitslice();
+        assert_eq!(boxed.count_ones(), Word::BITS * 2 - 2);
+        assert_eq!(boxed.count_zeros(), 1);
+
+        let boxed = bitvec![].into_boxed_bitslice();
+        assert_eq!(boxed.count_ones(), 0);
+        assert_eq!(boxed.count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_matches_bitvec() {
+        let len = Word::BITS * 2 + 5;
+        let mut vec: BitVec = (0..len).map(|index| index % 3 == 0).collect();
+        vec.push(true);
+        vec.pop();
+        let mut boxed = vec.clone().into_boxed_bitslice();
+
+        vec.fill_range(2..Word::BITS + 3, true);
+        boxed.fill_range(2..Word::BITS + 3, true);
+        assert!(boxed.iter().eq(vec.iter()));
+        vec.toggle_range(Word::BITS..);
+        boxed.toggle_range(Word::BITS..);
+        assert!(boxed.iter().eq(vec.iter()));
+        vec.copy_within(1..Word::BITS + 1, 4);
+        boxed.copy_within(1..Word::BITS + 1, 4);
+        assert!(boxed.iter().eq(vec.iter()));
+        vec.swap(0, len - 1);
+        boxed.swap(0, len - 1);
+        assert!(boxed.iter().eq(vec.iter()));
+        vec.reverse();
+        boxed.reverse();
+        assert!(boxed.iter().eq(vec.iter()));
+        vec.reverse_range(3..len - 2);
+        boxed.reverse_range(3..len - 2);
+        assert!(boxed.iter().eq(vec.iter()));
+
+        vec.store_le(5..21, 0x1234u16);
+        boxed.store_le(5..21, 0x1234u16);
+        vec.store(Word::BITS - 3..Word::BITS + 9, -7i16);
+        boxed.store(Word::BITS - 3..Word::BITS + 9, -7i16);
+        assert!(boxed.iter().eq(vec.iter()));
+        assert_eq!(boxed.load_le::<u16>(5..21), 0x1234);
+        assert_eq!(boxed.load::<i16>(Word::BITS - 3..Word::BITS + 9), -7);
+        assert_eq!(boxed.as_bitslice(), vec);
+
+        for pattern in [bitvec![], bitvec![true, true], bitvec![false, true, false]] {
+            assert_eq!(boxed.find(&pattern), vec.find(&pattern));
+            assert_eq!(boxed.rfind(&pattern), vec.rfind(&pattern));
+            assert!(boxed.find_iter(&pattern).eq(vec.find_iter(&pattern)));
+            assert!(
+                boxed
+                    .find_overlapping_iter(&pattern)
+                    .eq(vec.find_overlapping_iter(&pattern))
+            );
+            assert_eq!(boxed.starts_with(&pattern), vec.starts_with(&pattern));
+            assert_eq!(boxed.ends_with(&pattern), vec.ends_with(&pattern));
+        }
+
+        assert!(boxed.set_range(len..len + 1, true).is_none());
+        assert_eq!(boxed.fill(false).count_ones(), 0);
+    }
+
+    #[test]
+    fn test_hash_eq() {
+        fn hash(boxed: &BitBox) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            boxed.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let lhs = bitvec![true, true, false, false].into_boxed_bitslice();
+        let mut rhs = bitvec![true, true, false, false, true];
+        rhs.pop();
+        let rhs = rhs.into_boxed_bitslice();
+
+        assert_eq!(lhs, rhs);
+        assert_eq!(hash(&lhs), hash(&rhs));
+
+        let rhs = bitvec![true; 4].into_boxed_bitslice();
+
+        assert_ne!(lhs, rhs);
+        assert_ne!(hash(&lhs), hash(&rhs));
+    }
+}
@@ -0,0 +1,259 @@
+use super::BitBox;
+use crate::primitive::Word;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::boxed::Box;
+use allocator_api2::vec::Vec;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+impl<A: Allocator + Clone> BitBox<A> {
+    fn bitwise_operation<F>(&self, rhs: &Self, op: F) -> Self
+    where
+        F: FnMut((&Word, &Word)) -> Word,
+    {
+        let len = self.len.min(rhs.len);
+        let buf_len = len.div_ceil(Word::BITS);
+        let words = self.buf.iter().zip(rhs.buf.iter()).map(op);
+        let buf = collect_in(words, buf_len, Box::allocator(&self.buf).clone());
+        Self { len, buf }
+    }
+}
+
+impl<A: Allocator> BitBox<A> {
+    /// Reuses the buffer of `self`, which only has to shrink when `rhs` is
+    /// shorter.
+    fn bitwise_operation_consume_self<F>(self, rhs: &Self, mut op: F) -> Self
+    where
+        F: FnMut((Word, &Word)) -> Word,
+    {
+        let len = self.len.min(rhs.len);
+        let buf_len = len.div_ceil(Word::BITS);
+        let mut buf = self.buf.into_vec();
+        buf.truncate(buf_len);
+        for (left, right) in buf.iter_mut().zip(rhs.buf.iter()) {
+            *left = op((*left, right));
+        }
+        let buf = buf.into_boxed_slice();
+        Self { len, buf }
+    }
+}
+
+fn collect_in<I, A>(words: I, buf_len: usize, alloc: A) -> Box<[Word], A>
+where
+    I: Iterator<Item = Word>,
+    A: Allocator,
+{
+    let mut buf = Vec::with_capacity_in(buf_len, alloc);
+    buf.extend(words.take(buf_len));
+    buf.into_boxed_slice()
+}
+
+impl<A: Allocator> BitAnd for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `&` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitand(self, rhs: BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(&rhs, |(left, right)| left & *right)
+    }
+}
+
+impl<A: Allocator> BitAnd<&BitBox<A>> for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `&` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitand(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(rhs, |(left, right)| left & *right)
+    }
+}
+
+impl<A: Allocator> BitAnd<BitBox<A>> for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `&` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitand(self, rhs: BitBox<A>) -> Self::Output {
+        rhs & self
+    }
+}
+
+impl<A: Allocator + Clone> BitAnd for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `&` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitand(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left & *right)
+    }
+}
+
+impl<A: Allocator> BitOr for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `|` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitor(self, rhs: BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(&rhs, |(left, right)| left | *right)
+    }
+}
+
+impl<A: Allocator> BitOr<&BitBox<A>> for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `|` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitor(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(rhs, |(left, right)| left | *right)
+    }
+}
+
+impl<A: Allocator> BitOr<BitBox<A>> for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `|` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitor(self, rhs: BitBox<A>) -> Self::Output {
+        rhs | self
+    }
+}
+
+impl<A: Allocator + Clone> BitOr for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `|` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitor(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left | *right)
+    }
+}
+
+impl<A: Allocator> BitXor for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `^` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitxor(self, rhs: BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(&rhs, |(left, right)| left ^ *right)
+    }
+}
+
+impl<A: Allocator> BitXor<&BitBox<A>> for BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `^` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitxor(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation_consume_self(rhs, |(left, right)| left ^ *right)
+    }
+}
+
+impl<A: Allocator> BitXor<BitBox<A>> for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `^` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitxor(self, rhs: BitBox<A>) -> Self::Output {
+        rhs ^ self
+    }
+}
+
+impl<A: Allocator + Clone> BitXor for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    /// Performs the `^` operation, returning a new `BitBox` with the length of the
+    /// shorter input.
+    #[inline]
+    fn bitxor(self, rhs: &BitBox<A>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left ^ *right)
+    }
+}
+
+impl<A: Allocator> Not for BitBox<A> {
+    type Output = BitBox<A>;
+
+    #[inline]
+    fn not(mut self) -> Self::Output {
+        self.buf.iter_mut().for_each(|word| *word = !*word);
+        self
+    }
+}
+
+impl<A: Allocator + Clone> Not for &BitBox<A> {
+    type Output = BitBox<A>;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        let len = self.len;
+        let words = self.buf.iter().map(|word| !*word);
+        let buf = collect_in(words, self.buf.len(), Box::allocator(&self.buf).clone());
+        BitBox { len, buf }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bitvec;
+    use crate::primitive::Word;
+
+    const LONG: usize = Word::BITS * 2 + 1;
+    const SHORT: usize = Word::BITS + 1;
+
+    macro_rules! bitwise_assert {
+        ($op:tt, ($input_1:expr, $input_2:expr) => $output:expr) => {
+            let lhs = bitvec![$input_1; LONG].into_boxed_bitslice();
+            let rhs = bitvec![$input_2; SHORT].into_boxed_bitslice();
+            let expected = bitvec![$output; SHORT].into_boxed_bitslice();
+
+            assert_eq!(lhs.clone() $op rhs.clone(), expected);
+            assert_eq!(lhs.clone() $op &rhs, expected);
+            assert_eq!(&lhs $op rhs.clone(), expected);
+            assert_eq!(&lhs $op &rhs, expected);
+
+            assert_eq!(rhs.clone() $op lhs.clone(), expected);
+            assert_eq!(rhs.clone() $op &lhs, expected);
+            assert_eq!(&rhs $op lhs.clone(), expected);
+            assert_eq!(&rhs $op &lhs, expected);
+        };
+    }
+
+    #[test]
+    fn test_bitwise() {
+        bitwise_assert!(&, (true, false) => false);
+        bitwise_assert!(&, (true, true) => true);
+        bitwise_assert!(|, (false, false) => false);
+        bitwise_assert!(|, (true, false) => true);
+        bitwise_assert!(^, (true, false) => true);
+        bitwise_assert!(^, (true, true) => false);
+    }
+
+    #[test]
+    fn test_not() {
+        let lhs = bitvec![true; LONG].into_boxed_bitslice();
+        let rhs = bitvec![false; SHORT].into_boxed_bitslice();
+
+        assert_eq!(!&lhs, bitvec![false; LONG].into_boxed_bitslice());
+        assert_eq!(!rhs, bitvec![true; SHORT].into_boxed_bitslice());
+    }
+
+    #[test]
+    fn test_reuse_buffer() {
+        let lhs = bitvec![true; SHORT].into_boxed_bitslice();
+        let rhs = bitvec![false; LONG].into_boxed_bitslice();
+        let ptr = lhs.buf.as_ptr();
+
+        let result = lhs | &rhs;
+        assert_eq!(result.buf.as_ptr(), ptr);
+        assert_eq!(result, bitvec![true; SHORT].into_boxed_bitslice());
+    }
+}
@@ -1,9 +1,89 @@
-use crate::BitVec;
 use crate::primitive::{Bit, Byte, Word};
+use crate::{BitVec, TryReserveError, reserve_error};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec as BufVec;
 use core::ptr;
 
+mod integer;
+
+impl BitVec {
+    /// Creates a [`BitVec`] from a byte slice, returning an error instead of
+    /// panicking or aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of bits exceeds `usize::MAX`, or if the
+    /// allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vec = BitVec::try_from_bytes(&[0b11110000, 0b00001111]).unwrap();
+    /// assert_eq!(vec, bitvec![0b11110000, 0b00001111]);
+    /// ```
+    pub fn try_from_bytes(value: &[Byte]) -> Result<Self, TryReserveError> {
+        let len = value
+            .len()
+            .checked_mul(Byte::BITS as usize)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let buf_len = value.len().div_ceil(Word::BYTES);
+        let mut buf = BufVec::new();
+        buf.try_reserve_exact(buf_len)
+            .map_err(|_| reserve_error(buf_len))?;
+        unsafe {
+            write_bytes(value, &mut buf);
+        }
+        Ok(Self { len, buf })
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns the bytes holding the bits of the vector, with the padding bits
+    /// after the last one cleared.
+    #[cfg(any(feature = "serde", feature = "std"))]
+    #[inline]
+    pub(crate) fn to_bytes(&self) -> Vec<Byte> {
+        unsafe { words_to_bytes(&self.buf, self.len) }
+    }
+}
+
+/// Returns the bytes holding the first `len` bits of `buf`, with the padding
+/// bits after the last one cleared.
+///
+/// # Safety
+///
+/// The invariant `buf.len() >= len.div_ceil(Word::BITS)` must hold.
+#[cfg(any(feature = "serde", feature = "std"))]
+pub(crate) unsafe fn words_to_bytes(buf: &[Word], len: usize) -> Vec<Byte> {
+    let byte_len = len.div_ceil(Byte::BITS as usize);
+    let head_words = byte_len / Word::BYTES;
+    let tail_bytes = byte_len % Word::BYTES;
+    let mut bytes = Vec::with_capacity(byte_len);
+
+    let head = unsafe { buf.get_unchecked(..head_words) };
+    for word in head {
+        bytes.extend_from_slice(&word.to_byte_array());
+    }
+
+    if tail_bytes != 0 {
+        let word = unsafe { buf.get_unchecked(head_words) };
+        let word = word.to_byte_array();
+        let tail = unsafe { word.get_unchecked(0..tail_bytes) };
+        bytes.extend_from_slice(tail);
+    }
+
+    let used = len % Byte::BITS as usize;
+    if let Some(last) = bytes.last_mut().filter(|_| used != 0) {
+        *last &= !(Byte::MAX >> used);
+    }
+
+    bytes
+}
+
 impl From<&[Byte]> for BitVec {
     fn from(value: &[Byte]) -> Self {
         let len = value
@@ -11,30 +91,41 @@ impl From<&[Byte]> for BitVec {
             .checked_mul(Byte::BITS as usize)
             .expect("capacity overflow");
         let buf_len = value.len().div_ceil(Word::BYTES);
-        let mut buf = Vec::with_capacity(buf_len);
-
-        let head = value.chunks_exact(Word::BYTES);
-        let tail = head.remainder();
-
+        let mut buf = BufVec::with_capacity(buf_len);
         unsafe {
-            let mut dst: *mut Word = buf.as_mut_ptr();
-
-            for chunk in head {
-                let word = chunk.try_into().unwrap_unchecked();
-                let word = Word::from_byte_array(word);
-                ptr::write(dst, word);
-                dst = dst.add(1);
-            }
+            write_bytes(value, &mut buf);
+        }
+        Self { len, buf }
+    }
+}
 
-            if !tail.is_empty() {
-                let word = Word::from_byte_slice(tail);
-                ptr::write(dst, word);
-            }
+/// Packs `value` into words and writes them to the empty `buf`.
+///
+/// # Safety
+///
+/// The invariant `buf.capacity() >= value.len().div_ceil(Word::BYTES)` must hold.
+unsafe fn write_bytes(value: &[Byte], buf: &mut BufVec<Word>) {
+    let buf_len = value.len().div_ceil(Word::BYTES);
+
+    let head = value.chunks_exact(Word::BYTES);
+    let tail = head.remainder();
+
+    unsafe {
+        let mut dst: *mut Word = buf.as_mut_ptr();
+
+        for chunk in head {
+            let word = chunk.try_into().unwrap_unchecked();
+            let word = Word::from_byte_array(word);
+            ptr::write(dst, word);
+            dst = dst.add(1);
+        }
 
-            buf.set_len(buf_len);
+        if !tail.is_empty() {
+            let word = Word::from_byte_slice(tail);
+            ptr::write(dst, word);
         }
 
-        Self { len, buf }
+        buf.set_len(buf_len);
     }
 }
 
@@ -101,30 +192,30 @@ impl From<Vec<Bit>> for BitVec {
     }
 }
 
-impl From<&BitVec> for Box<[Bit]> {
+impl<A: Allocator> From<&BitVec<A>> for Box<[Bit]> {
     #[inline]
-    fn from(value: &BitVec) -> Self {
+    fn from(value: &BitVec<A>) -> Self {
         value.iter().collect()
     }
 }
 
-impl From<BitVec> for Box<[Bit]> {
+impl<A: Allocator> From<BitVec<A>> for Box<[Bit]> {
     #[inline]
-    fn from(value: BitVec) -> Self {
+    fn from(value: BitVec<A>) -> Self {
         value.into_iter().collect()
     }
 }
 
-impl From<&BitVec> for Vec<Bit> {
+impl<A: Allocator> From<&BitVec<A>> for Vec<Bit> {
     #[inline]
-    fn from(value: &BitVec) -> Self {
+    fn from(value: &BitVec<A>) -> Self {
         value.iter().collect()
     }
 }
 
-impl From<BitVec> for Vec<Bit> {
+impl<A: Allocator> From<BitVec<A>> for Vec<Bit> {
     #[inline]
-    fn from(value: BitVec) -> Self {
+    fn from(value: BitVec<A>) -> Self {
         value.into_iter().collect()
     }
 }
@@ -134,12 +225,33 @@ impl FromIterator<Bit> for BitVec {
     where
         I: IntoIterator<Item = Bit>,
     {
-        let iter = iter.into_iter();
-        let capacity = iter.size_hint().0;
-        let mut vec = BitVec::with_capacity(capacity);
-        for value in iter {
-            vec.push(value);
-        }
+        let mut vec = BitVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a> FromIterator<&'a Bit> for BitVec {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Bit>,
+    {
+        let mut vec = BitVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<A: Allocator> FromIterator<BitVec<A>> for BitVec {
+    /// Concatenates the vectors in order.
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = BitVec<A>>,
+    {
+        let mut vec = BitVec::new();
+        vec.extend(iter);
         vec
     }
 }
@@ -165,6 +277,16 @@ mod tests {
         assert_eq!(vec.buf, [Word::CLEAR; 2]);
     }
 
+    #[test]
+    fn test_try_from_bytes() {
+        let vec = BitVec::try_from_bytes(&[0b11110000]).unwrap();
+        assert_eq!(vec, BitVec::from([0b11110000]));
+
+        let vec = BitVec::try_from_bytes(&[0b00000000; Word::BYTES * 2]).unwrap();
+        assert_eq!(vec.len, Word::BITS * 2);
+        assert_eq!(vec.buf, [Word::CLEAR; 2]);
+    }
+
     #[test]
     fn test_from_bits() {
         let vec = BitVec::from([true, true, false, false]);
@@ -224,5 +346,10 @@ mod tests {
         let vec = BitVec::from_iter([false; Word::BITS * 2]);
         assert_eq!(vec.len, Word::BITS * 2);
         assert_eq!(vec.buf, [Word::CLEAR; 2]);
+        let vec = BitVec::from_iter(&[true, true, false, false]);
+        assert_eq!(vec, bitvec![true, true, false, false]);
+
+        let vec = BitVec::from_iter([bitvec![true, true], bitvec![false], bitvec![false]]);
+        assert_eq!(vec, bitvec![true, true, false, false]);
     }
 }
@@ -0,0 +1,157 @@
+use crate::{BitVec, TryFromBitVecError};
+use allocator_api2::alloc::Allocator;
+
+impl BitVec {
+    /// Creates a vector of `len` clear bits, checking that they fit in an
+    /// integer of `width` bits.
+    fn zeroed_field(len: usize, width: u32) -> Self {
+        assert!(
+            len <= width as usize,
+            "range of {len} bits does not fit in a {width}-bit integer"
+        );
+        Self::zeroed(len)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($ty:ident => $be:ident, $le:ident;)*) => {$(
+        impl BitVec {
+            #[doc = concat!("Creates a [`BitVec`] from the lowest `bits` bits of a [`", stringify!($ty), "`], with the")]
+            /// most significant bit first.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if `bits` exceeds the width of [`", stringify!($ty), "`].")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bitvek::{BitVec, bitvec};
+            ///
+            #[doc = concat!("let vec = BitVec::", stringify!($be), "(5, 3);")]
+            /// assert_eq!(vec, bitvec![true, false, true]);
+            /// ```
+            pub fn $be(value: $ty, bits: usize) -> Self {
+                let mut vec = Self::zeroed_field(bits, <$ty>::BITS);
+                vec.store_be(.., value);
+                vec
+            }
+
+            #[doc = concat!("Creates a [`BitVec`] from the lowest `bits` bits of a [`", stringify!($ty), "`] in")]
+            /// little-endian order, grouped as described in
+            /// [`load_le`](BitVec::load_le).
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if `bits` exceeds the width of [`", stringify!($ty), "`].")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bitvek::{BitVec, bitvec};
+            ///
+            #[doc = concat!("let vec = BitVec::", stringify!($le), "(5, 8);")]
+            /// assert_eq!(vec, bitvec![0b00000101]);
+            /// ```
+            pub fn $le(value: $ty, bits: usize) -> Self {
+                let mut vec = Self::zeroed_field(bits, <$ty>::BITS);
+                vec.store_le(.., value);
+                vec
+            }
+        }
+
+        impl From<$ty> for BitVec {
+            /// Converts the integer into a vector of all its bits, with the most
+            /// significant bit first.
+            #[inline]
+            fn from(value: $ty) -> Self {
+                Self::$be(value, <$ty>::BITS as usize)
+            }
+        }
+
+        impl<A: Allocator> TryFrom<&BitVec<A>> for $ty {
+            type Error = TryFromBitVecError;
+
+            /// Reads the vector as an integer with the first bit being the most
+            /// significant, failing if the vector is longer than the integer.
+            /// Signed integers are sign-extended from the first bit, which is the
+            /// most significant one.
+            #[inline]
+            fn try_from(value: &BitVec<A>) -> Result<Self, Self::Error> {
+                if value.len() > <$ty>::BITS as usize {
+                    return Err(TryFromBitVecError(()));
+                }
+                Ok(value.load_be(..))
+            }
+        }
+    )*};
+}
+
+impl_integer! {
+    u8 => from_u8_be, from_u8_le;
+    u16 => from_u16_be, from_u16_le;
+    u32 => from_u32_be, from_u32_le;
+    u64 => from_u64_be, from_u64_le;
+    u128 => from_u128_be, from_u128_le;
+    usize => from_usize_be, from_usize_le;
+    i8 => from_i8_be, from_i8_le;
+    i16 => from_i16_be, from_i16_le;
+    i32 => from_i32_be, from_i32_le;
+    i64 => from_i64_be, from_i64_le;
+    i128 => from_i128_be, from_i128_le;
+    isize => from_isize_be, from_isize_le;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+
+    #[test]
+    fn test_from_integer() {
+        assert_eq!(BitVec::from(0b10110011u8), bitvec![0b10110011]);
+        assert_eq!(BitVec::from(0x1234u16), bitvec![0x12, 0x34]);
+        assert_eq!(BitVec::from(-2i8), bitvec![0b11111110]);
+        assert_eq!(BitVec::from(u128::MAX), bitvec![true; 128]);
+
+        assert_eq!(
+            BitVec::from_u32_be(0x1234, 12),
+            bitvec![0x23, 0b0100_0000]
+                .iter()
+                .take(12)
+                .collect::<BitVec>()
+        );
+        assert_eq!(BitVec::from_u32_le(0x1234, 16), bitvec![0x34, 0x12]);
+        assert_eq!(
+            BitVec::from_i16_le(-2, 12),
+            bitvec![0xfe, 0xff].iter().take(12).collect::<BitVec>()
+        );
+        assert_eq!(BitVec::from_u64_be(u64::MAX, 0), bitvec![]);
+
+        let vec = BitVec::from_u64_be(0x0123_4567_89ab_cdef, 64);
+        assert_eq!(vec.len(), 64);
+        assert_eq!(vec.load::<u64>(..), 0x0123_4567_89ab_cdef);
+    }
+
+    #[test]
+    #[should_panic(expected = "range of 9 bits does not fit in a 8-bit integer")]
+    fn test_from_integer_fail() {
+        BitVec::from_u8_be(0, 9);
+    }
+
+    #[test]
+    fn test_try_from_bitvec() {
+        let mut vec = bitvec![0b10110011, 0b01010101];
+        vec.push_unused_word();
+
+        assert_eq!(u16::try_from(&vec), Ok(0b10110011_01010101));
+        assert_eq!(i16::try_from(&vec), Ok(0b10110011_01010101u16 as i16));
+        assert_eq!(u64::try_from(&vec), Ok(0b10110011_01010101));
+        assert_eq!(u8::try_from(&vec), Err(TryFromBitVecError(())));
+        assert_eq!(u8::try_from(&BitVec::new()), Ok(0));
+
+        for value in [0, 1, -1, i32::MIN, i32::MAX, 0x1234_5678] {
+            assert_eq!(i32::try_from(&BitVec::from(value)), Ok(value));
+        }
+    }
+}
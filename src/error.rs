@@ -0,0 +1,174 @@
+use core::error::Error;
+use core::fmt;
+
+/// The error type for `try_*` methods that may allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The required capacity exceeds `usize::MAX` bits, or the buffer would
+    /// exceed `isize::MAX` bytes.
+    CapacityOverflow,
+
+    /// The memory allocator returned an error.
+    AllocError,
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")?;
+        let reason = match self {
+            Self::CapacityOverflow => " because the computed capacity exceeded the maximum",
+            Self::AllocError => " because the memory allocator returned an error",
+        };
+        f.write_str(reason)
+    }
+}
+
+impl Error for TryReserveError {}
+
+/// The error type returned when a checked conversion from a [`BitVec`] into a
+/// primitive integer fails because the vector holds more bits than the integer.
+///
+/// [`BitVec`]: crate::BitVec
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBitVecError(pub(crate) ());
+
+impl fmt::Display for TryFromBitVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bit vector is too long for the target integer type")
+    }
+}
+
+impl Error for TryFromBitVecError {}
+
+/// The error type returned when a [`BitReader`] has fewer bits left than an
+/// operation requires. The reader is left unchanged.
+///
+/// [`BitReader`]: crate::BitReader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnderrunError {
+    /// The number of bits the operation required.
+    pub requested: usize,
+
+    /// The number of bits left in the reader.
+    pub available: usize,
+}
+
+impl fmt::Display for UnderrunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "attempted to read {} bits with only {} remaining",
+            self.requested, self.available
+        )
+    }
+}
+
+impl Error for UnderrunError {}
+
+/// The error type for the integer code decoders of [`BitReader`], such as
+/// [`BitReader::read_elias_gamma`]. The reader is left unchanged.
+///
+/// [`BitReader`]: crate::BitReader
+/// [`BitReader::read_elias_gamma`]: crate::BitReader::read_elias_gamma
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The reader ended in the middle of a code.
+    Underrun(UnderrunError),
+
+    /// The code is malformed, or represents a value out of range for the
+    /// result type.
+    Overflow,
+}
+
+impl From<UnderrunError> for DecodeError {
+    #[inline]
+    fn from(value: UnderrunError) -> Self {
+        Self::Underrun(value)
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Underrun(error) => write!(f, "failed to decode integer: {error}"),
+            Self::Overflow => f.write_str("failed to decode integer: value out of range"),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Underrun(error) => Some(error),
+            Self::Overflow => None,
+        }
+    }
+}
+
+/// The error type for [`BitVec::read_from`].
+///
+/// [`BitVec::read_from`]: crate::BitVec::read_from
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadError {
+    /// The reader returned an error.
+    Io(std::io::Error),
+
+    /// The input ended before the end of the container.
+    Truncated,
+
+    /// The input does not start with the container magic bytes.
+    BadMagic,
+
+    /// The container was written by an unsupported version of the format.
+    UnsupportedVersion(u8),
+
+    /// The container declares an unknown bit order.
+    UnsupportedBitOrder(u8),
+
+    /// The declared length exceeds `usize::MAX` bits.
+    TooLong(u64),
+
+    /// The checksum of the input does not match its trailer.
+    ChecksumMismatch {
+        /// The checksum stored in the trailer.
+        expected: u32,
+        /// The checksum computed from the input.
+        actual: u32,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read bit vector: {error}"),
+            Self::Truncated => f.write_str("bit vector container is truncated"),
+            Self::BadMagic => f.write_str("input is not a bit vector container"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported bit vector container version {version}")
+            }
+            Self::UnsupportedBitOrder(order) => {
+                write!(f, "unsupported bit order {order} in bit vector container")
+            }
+            Self::TooLong(len) => write!(f, "bit vector of {len} bits is too long"),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "bit vector container is corrupted: checksum {actual:#010x} does not match {expected:#010x}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use crate::primitive::Word;
+use crate::slice::{load_bits, store_bits};
+use crate::{BitVec, resolve_range};
+use allocator_api2::alloc::Allocator;
+use core::ops::{Range, RangeBounds};
+
+/// A primitive integer type that can be loaded from or stored into a range of
+/// bits.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Integer: Copy + sealed::Sealed {}
+
+pub(crate) mod sealed {
+    pub trait Sealed {
+        const BITS: usize;
+
+        /// Converts the lowest `width` bits of `bits`, sign-extending them for
+        /// signed types.
+        fn from_bits(bits: u128, width: usize) -> Self;
+
+        /// Converts `self` into bits, sign-extending it for signed types.
+        fn to_bits(self) -> u128;
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {$(
+        impl sealed::Sealed for $ty {
+            const BITS: usize = <$ty>::BITS as usize;
+
+            #[inline]
+            fn from_bits(bits: u128, _width: usize) -> Self {
+                bits as Self
+            }
+
+            #[inline]
+            fn to_bits(self) -> u128 {
+                self as u128
+            }
+        }
+
+        impl Integer for $ty {}
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {$(
+        impl sealed::Sealed for $ty {
+            const BITS: usize = <$ty>::BITS as usize;
+
+            #[inline]
+            fn from_bits(bits: u128, width: usize) -> Self {
+                if width == 0 {
+                    return 0;
+                }
+                let shift = u128::BITS as usize - width;
+                ((bits << shift) as i128 >> shift) as Self
+            }
+
+            #[inline]
+            fn to_bits(self) -> u128 {
+                self as u128
+            }
+        }
+
+        impl Integer for $ty {}
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<A: Allocator> BitVec<A> {
+    /// Loads the bits in `range` as an integer in big-endian order, which is
+    /// the same as [`load_be`](BitVec::load_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![0b10110011, 0b01010101];
+    /// assert_eq!(vec.load::<u8>(4..12), 0b00110101);
+    /// assert_eq!(vec.load::<i8>(0..4), -5);
+    /// ```
+    #[inline]
+    pub fn load<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        self.load_be(range)
+    }
+
+    /// Loads the bits in `range` as an integer, with the first bit being the
+    /// most significant. Signed integers are sign-extended from the first bit of
+    /// the range, which is the most significant one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![0b10110011, 0b01010101];
+    /// assert_eq!(vec.load_be::<u16>(..), 0b10110011_01010101);
+    /// assert_eq!(vec.load_be::<u16>(2..14), 0b110011_010101);
+    /// ```
+    pub fn load_be<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field_range::<T>(range, self.len);
+        unsafe { load_be(&self.buf, range) }
+    }
+
+    /// Loads the bits in `range` as an integer in little-endian order.
+    ///
+    /// The range is split into groups of 8 bits from its start, and the groups
+    /// are ordered from least to most significant, with the bits within each
+    /// group being ordered from most to least significant. A shorter final group
+    /// holds the most significant bits. Signed integers are sign-extended from
+    /// the first bit of the final group, which is the most significant one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![0b10110011, 0b01010101];
+    /// assert_eq!(vec.load_le::<u16>(..), 0b01010101_10110011);
+    /// assert_eq!(vec.load_le::<u16>(0..12), 0b0101_10110011);
+    /// ```
+    pub fn load_le<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field_range::<T>(range, self.len);
+        unsafe { load_le(&self.buf, range) }
+    }
+
+    /// Stores the lowest bits of `value` into `range` in big-endian order, which
+    /// is the same as [`store_be`](BitVec::store_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![0b00000000, 0b00000000];
+    /// vec.store(4..12, 0xffu8);
+    /// assert_eq!(vec, bitvec![0b00001111, 0b11110000]);
+    /// ```
+    #[inline]
+    pub fn store<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        self.store_be(range, value);
+    }
+
+    /// Stores the lowest bits of `value` into `range`, with the first bit being
+    /// the most significant. Bits of `value` that do not fit are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![0b00000000, 0b00000000];
+    /// vec.store_be(2..14, -1i16);
+    /// assert_eq!(vec, bitvec![0b00111111, 0b11111100]);
+    /// ```
+    pub fn store_be<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field_range::<T>(range, self.len);
+        unsafe { store_be(&mut self.buf, range, value) };
+    }
+
+    /// Stores the lowest bits of `value` into `range` in little-endian order,
+    /// using the same grouping as [`load_le`](BitVec::load_le). Bits of `value`
+    /// that do not fit are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![0b00000000, 0b00000000];
+    /// vec.store_le(.., 0x1234u16);
+    /// assert_eq!(vec, bitvec![0x34, 0x12]);
+    /// ```
+    pub fn store_le<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field_range::<T>(range, self.len);
+        unsafe { store_le(&mut self.buf, range, value) };
+    }
+}
+
+/// Resolves `range` against `len`, checking that it fits in a `T`.
+pub(crate) fn field_range<T: Integer>(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let range = resolve_range(range, len);
+    assert!(
+        range.len() <= T::BITS,
+        "range of {} bits does not fit in a {}-bit integer",
+        range.len(),
+        T::BITS
+    );
+    range
+}
+
+/// # Safety
+///
+/// The range must lie within `buf` and span at most `T::BITS` bits.
+pub(crate) unsafe fn load_be<T: Integer>(buf: &[Word], range: Range<usize>) -> T {
+    let bits = unsafe { load_bits(buf, range.clone()) };
+    T::from_bits(bits, range.len())
+}
+
+/// # Safety
+///
+/// The range must lie within `buf` and span at most `T::BITS` bits.
+pub(crate) unsafe fn load_le<T: Integer>(buf: &[Word], range: Range<usize>) -> T {
+    let mut bits = 0;
+    for (index, start) in range.clone().step_by(8).enumerate() {
+        let end = range.end.min(start + 8);
+        bits |= unsafe { load_bits(buf, start..end) } << (index * 8);
+    }
+    T::from_bits(bits, range.len())
+}
+
+/// # Safety
+///
+/// The range must lie within `buf` and span at most `T::BITS` bits.
+pub(crate) unsafe fn store_be<T: Integer>(buf: &mut [Word], range: Range<usize>, value: T) {
+    unsafe { store_bits(buf, range, value.to_bits()) };
+}
+
+/// # Safety
+///
+/// The range must lie within `buf` and span at most `T::BITS` bits.
+pub(crate) unsafe fn store_le<T: Integer>(buf: &mut [Word], range: Range<usize>, value: T) {
+    let bits = value.to_bits();
+    for (index, start) in range.clone().step_by(8).enumerate() {
+        let end = range.end.min(start + 8);
+        unsafe { store_bits(buf, start..end, bits >> (index * 8)) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+    use crate::primitive::Word;
+
+    #[test]
+    fn test_load() {
+        let mut vec = bitvec![0b10110011, 0b01010101, 0b11110000];
+        vec.push_unused_word();
+
+        assert_eq!(vec.load::<u8>(0..8), 0b10110011);
+        assert_eq!(vec.load::<u32>(4..20), 0x355f);
+        assert_eq!(vec.load::<u8>(3..3), 0);
+        assert_eq!(vec.load::<i8>(0..4), -5);
+        assert_eq!(vec.load::<i8>(1..4), 3);
+        assert_eq!(
+            vec.load::<i32>(0..24),
+            0b10110011_01010101_11110000 - 0x100_0000
+        );
+        assert_eq!(vec.load::<i128>(..=0), -1);
+
+        assert_eq!(vec.load_le::<u32>(..), 0b11110000_01010101_10110011);
+        assert_eq!(vec.load_le::<u32>(4..20), 0b01011111_00110101);
+        assert_eq!(vec.load_le::<i16>(0..12), 0b0101_10110011);
+        assert_eq!(vec.load_le::<i16>(4..16), 0b0101_00110101);
+        assert_eq!(vec.load_le::<i16>(8..20), 0b1111_01010101 - 0x1000);
+    }
+
+    #[test]
+    fn test_load_across_words() {
+        let vec = BitVec::from_iter((0..Word::BITS * 3).map(|index| index % 3 == 0));
+        for start in [0, 1, Word::BITS - 3, Word::BITS, Word::BITS + 5] {
+            let expected = vec
+                .iter()
+                .skip(start)
+                .take(64)
+                .fold(0u64, |acc, bit| acc << 1 | bit as u64);
+            assert_eq!(vec.load::<u64>(start..start + 64), expected);
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        for start in [0, 1, 7, Word::BITS - 3, Word::BITS + 5] {
+            let mut vec = BitVec::from_iter([true; Word::BITS * 3]);
+            vec.store(start..start + 40, 0x12_3456_789au64);
+            assert_eq!(vec.load::<u64>(start..start + 40), 0x12_3456_789a);
+            assert_eq!(vec.load::<i64>(start..start + 40), 0x12_3456_789a);
+            assert!(vec.iter().take(start).all(|bit| bit));
+            assert!(vec.iter().skip(start + 40).all(|bit| bit));
+
+            vec.store_le(start..start + 36, -2i64);
+            assert_eq!(vec.load_le::<i64>(start..start + 36), -2);
+            assert_eq!(vec.load_le::<u64>(start..start + 36), 0xf_ffff_fffe);
+            assert_eq!(vec.load_be::<u8>(start..start + 8), 0xfe);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "range of 9 bits does not fit in a 8-bit integer")]
+    fn test_load_fail() {
+        let vec = bitvec![0b10110011, 0b01010101];
+        vec.load::<u8>(0..9);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 17 out of range for slice of length 16")]
+    fn test_store_fail() {
+        let mut vec = bitvec![0b10110011, 0b01010101];
+        vec.store(9..17, 0u8);
+    }
+}
@@ -0,0 +1,421 @@
+use crate::primitive::{Byte, Word};
+use crate::{BitVec, ReadError};
+use allocator_api2::alloc::Allocator;
+use std::io::{self, ErrorKind, Read, Write};
+use std::vec::Vec;
+
+const MAGIC: [Byte; 4] = *b"BITV";
+const VERSION: u8 = 1;
+const MSB_FIRST: u8 = 0;
+const LSB_FIRST: u8 = 1;
+const HEADER_LEN: usize = 14;
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns a reader over the bytes of the vector, most significant bit
+    /// first, with the last byte padded with zero bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    /// use std::io::Read;
+    ///
+    /// let vec = bitvec![0b10100101, 0b11110000];
+    /// let mut bytes = Vec::new();
+    /// vec.byte_reader().read_to_end(&mut bytes)?;
+    /// assert_eq!(bytes, [0b10100101, 0b11110000]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn byte_reader(&self) -> ByteReader<'_> {
+        let buf = &self.buf;
+        let len = self.len;
+        let pos = 0;
+        ByteReader { buf, len, pos }
+    }
+}
+
+/// Appends each byte written as 8 bits, most significant bit first.
+///
+/// # Examples
+///
+/// ```
+/// use bitvek::bitvec;
+/// use std::io::Write;
+///
+/// let mut vec = bitvec![true];
+/// vec.write_all(&[0b11110000])?;
+/// assert_eq!(vec, bitvec![true, true, true, true, true, false, false, false, false]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+impl<A: Allocator> Write for BitVec<A> {
+    fn write(&mut self, buf: &[Byte]) -> io::Result<usize> {
+        for chunk in buf.chunks(Word::BYTES) {
+            let word = unsafe { Word::from_byte_slice(chunk) };
+            self.extend_words(&[word], chunk.len() * Byte::BITS as usize);
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader over the bytes of a [`BitVec`].
+///
+/// This struct is created by [`BitVec::byte_reader`].
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    buf: &'a [Word],
+    len: usize,
+    pos: usize,
+}
+
+impl ByteReader<'_> {
+    fn byte_len(&self) -> usize {
+        self.len.div_ceil(Byte::BITS as usize)
+    }
+
+    fn byte_at(&self, index: usize) -> Byte {
+        let period = index / Word::BYTES;
+        let word = unsafe { self.buf.get_unchecked(period) };
+        let byte = word.to_byte_array()[index % Word::BYTES];
+        let used = self.len - index * Byte::BITS as usize;
+        if used < Byte::BITS as usize {
+            byte & !(Byte::MAX >> used)
+        } else {
+            byte
+        }
+    }
+}
+
+impl Read for ByteReader<'_> {
+    fn read(&mut self, buf: &mut [Byte]) -> io::Result<usize> {
+        let count = buf.len().min(self.byte_len() - self.pos);
+        for (offset, byte) in buf[..count].iter_mut().enumerate() {
+            *byte = self.byte_at(self.pos + offset);
+        }
+        self.pos += count;
+        Ok(count)
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
+    /// Writes the vector to `writer` in a self-describing binary container.
+    ///
+    /// The container consists of
+    ///
+    /// - the magic bytes `BITV`,
+    /// - a format version byte, currently `1`,
+    /// - a bit order byte, `0` for most significant bit first and `1` for
+    ///   least significant bit first,
+    /// - the length in bits as a big-endian `u64`,
+    /// - the bits packed into bytes in the declared order, padded with zero
+    ///   bits,
+    /// - and a big-endian CRC-32 (IEEE) of all the preceding bytes.
+    ///
+    /// Vectors are always written most significant bit first.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vec = bitvec![true, false, true];
+    /// let mut file = Vec::new();
+    /// vec.write_to(&mut file)?;
+    /// assert_eq!(BitVec::read_from(&file[..])?, vec);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut header = [0; HEADER_LEN];
+        header[..4].copy_from_slice(&MAGIC);
+        header[4] = VERSION;
+        header[5] = MSB_FIRST;
+        header[6..].copy_from_slice(&(self.len as u64).to_be_bytes());
+        let payload = self.to_bytes();
+
+        let mut crc = Crc32::new();
+        crc.update(&header);
+        crc.update(&payload);
+
+        writer.write_all(&header)?;
+        writer.write_all(&payload)?;
+        writer.write_all(&crc.finish().to_be_bytes())
+    }
+}
+
+impl BitVec {
+    /// Reads a vector from `reader`, in the container written by
+    /// [`BitVec::write_to`].
+    ///
+    /// Bytes after the container are left unread.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReadError`] if the reader fails, or if the input is
+    /// truncated, corrupted or written by an unsupported version of the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, ReadError, bitvec};
+    ///
+    /// let mut file = Vec::new();
+    /// bitvec![true, false, true].write_to(&mut file)?;
+    ///
+    /// let last = file.len() - 1;
+    /// file[last] ^= 1;
+    /// assert!(matches!(
+    ///     BitVec::read_from(&file[..]),
+    ///     Err(ReadError::ChecksumMismatch { .. })
+    /// ));
+    ///
+    /// file.pop();
+    /// assert!(matches!(BitVec::read_from(&file[..]), Err(ReadError::Truncated)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, ReadError> {
+        let mut header = [0; HEADER_LEN];
+        read_exact(&mut reader, &mut header[..4])?;
+        if header[..4] != MAGIC {
+            return Err(ReadError::BadMagic);
+        }
+        read_exact(&mut reader, &mut header[4..])?;
+        if header[4] != VERSION {
+            return Err(ReadError::UnsupportedVersion(header[4]));
+        }
+        let order = header[5];
+        if order != MSB_FIRST && order != LSB_FIRST {
+            return Err(ReadError::UnsupportedBitOrder(order));
+        }
+        let len = u64::from_be_bytes(header[6..].try_into().unwrap());
+        let len = usize::try_from(len).map_err(|_| ReadError::TooLong(len))?;
+
+        // Read through `take` rather than allocating the whole payload up
+        // front, so that a corrupted length cannot trigger a huge allocation.
+        let payload_len = len.div_ceil(Byte::BITS as usize);
+        let mut payload = Vec::new();
+        (&mut reader)
+            .take(payload_len as u64)
+            .read_to_end(&mut payload)
+            .map_err(ReadError::Io)?;
+        if payload.len() != payload_len {
+            return Err(ReadError::Truncated);
+        }
+
+        let mut trailer = [0; 4];
+        read_exact(&mut reader, &mut trailer)?;
+        let mut crc = Crc32::new();
+        crc.update(&header);
+        crc.update(&payload);
+        let expected = u32::from_be_bytes(trailer);
+        let actual = crc.finish();
+        if expected != actual {
+            return Err(ReadError::ChecksumMismatch { expected, actual });
+        }
+
+        if order == LSB_FIRST {
+            payload
+                .iter_mut()
+                .for_each(|byte| *byte = byte.reverse_bits());
+        }
+        let mut vec = BitVec::from(payload);
+        vec.len = len;
+        Ok(vec)
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [Byte]) -> Result<(), ReadError> {
+    reader.read_exact(buf).map_err(|error| match error.kind() {
+        ErrorKind::UnexpectedEof => ReadError::Truncated,
+        _ => ReadError::Io(error),
+    })
+}
+
+/// A CRC-32 checksum with the IEEE polynomial, as used by zlib and PNG.
+#[derive(Debug, Clone, Copy)]
+struct Crc32(u32);
+
+impl Crc32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut index = 0;
+        while index < 256 {
+            let mut crc = index as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    crc >> 1 ^ 0xedb88320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[index] = crc;
+            index += 1;
+        }
+        table
+    };
+
+    fn new() -> Self {
+        Self(!0)
+    }
+
+    fn update(&mut self, bytes: &[Byte]) {
+        for &byte in bytes {
+            let index = (self.0 as u8 ^ byte) as usize;
+            self.0 = self.0 >> 8 ^ Self::TABLE[index];
+        }
+    }
+
+    fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+
+    fn container(vec: &BitVec) -> Vec<Byte> {
+        let mut file = Vec::new();
+        vec.write_to(&mut file).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_write() {
+        let bytes: Vec<Byte> = (0..20).map(|byte| byte * 13).collect();
+        for prefix in [0, 3, Word::BITS] {
+            let mut vec: BitVec = (0..prefix).map(|index| index % 2 == 0).collect();
+            let mut expected = vec.clone();
+            expected.extend([BitVec::from(&bytes[..])]);
+            vec.push(true).pop();
+            vec.push_unused_word();
+            vec.write_all(&bytes[..7]).unwrap();
+            vec.write_all(&bytes[7..]).unwrap();
+            assert_eq!(vec, expected);
+        }
+    }
+
+    #[test]
+    fn test_byte_reader() {
+        for len in [0usize, 3, 8, Word::BITS + 5, Word::BITS * 2] {
+            let mut vec: BitVec = (0..len).map(|index| index % 3 != 0).collect();
+            vec.push(true).pop();
+            vec.push_unused_word();
+
+            let mut bytes = Vec::new();
+            vec.byte_reader().read_to_end(&mut bytes).unwrap();
+            assert_eq!(bytes, vec.to_bytes());
+
+            let mut reader = vec.byte_reader();
+            let mut chunk = [0; 3];
+            let mut chunked = Vec::new();
+            loop {
+                let count = reader.read(&mut chunk).unwrap();
+                if count == 0 {
+                    break;
+                }
+                chunked.extend_from_slice(&chunk[..count]);
+            }
+            assert_eq!(chunked, bytes);
+        }
+    }
+
+    #[test]
+    fn test_crc32() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finish(), 0xcbf43926);
+        assert_eq!(Crc32::new().finish(), 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in [0usize, 1, 8, 9, 64, 100] {
+            let mut vec: BitVec = (0..len).map(|index| index % 3 == 0).collect();
+            vec.push(true).pop();
+            let file = container(&vec);
+            assert_eq!(file.len(), HEADER_LEN + len.div_ceil(8) + 4);
+            assert_eq!(BitVec::read_from(&file[..]).unwrap(), vec);
+        }
+
+        let vec = bitvec![true, false, true];
+        let file = container(&vec);
+        assert_eq!(file[..6], *b"BITV\x01\x00");
+        assert_eq!(file[6..14], 3u64.to_be_bytes());
+        assert_eq!(file[14], 0b10100000);
+
+        let files = [container(&vec), container(&!vec.clone())].concat();
+        let mut reader = &files[..];
+        assert_eq!(BitVec::read_from(&mut reader).unwrap(), vec);
+        assert_eq!(BitVec::read_from(&mut reader).unwrap(), !vec);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_lsb_first() {
+        let mut file = Vec::from(*b"BITV\x01\x01");
+        file.extend_from_slice(&3u64.to_be_bytes());
+        file.push(0b00000101);
+        let mut crc = Crc32::new();
+        crc.update(&file);
+        file.extend_from_slice(&crc.finish().to_be_bytes());
+        assert_eq!(
+            BitVec::read_from(&file[..]).unwrap(),
+            bitvec![true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let file = container(&bitvec![true, false, true]);
+        for len in 0..file.len() {
+            let error = BitVec::read_from(&file[..len]).unwrap_err();
+            assert!(matches!(error, ReadError::Truncated), "{len}: {error}");
+        }
+
+        let mut bad = file.clone();
+        bad[0] = b'X';
+        assert!(matches!(
+            BitVec::read_from(&bad[..]),
+            Err(ReadError::BadMagic)
+        ));
+
+        let mut bad = file.clone();
+        bad[4] = 2;
+        assert!(matches!(
+            BitVec::read_from(&bad[..]),
+            Err(ReadError::UnsupportedVersion(2))
+        ));
+
+        let mut bad = file.clone();
+        bad[5] = 7;
+        assert!(matches!(
+            BitVec::read_from(&bad[..]),
+            Err(ReadError::UnsupportedBitOrder(7))
+        ));
+
+        for index in 6..file.len() {
+            let mut bad = file.clone();
+            bad[index] ^= 0x10;
+            let error = BitVec::read_from(&bad[..]).unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    ReadError::ChecksumMismatch { .. } | ReadError::Truncated
+                ),
+                "{index}: {error}"
+            );
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use crate::BitVec;
-use crate::primitive::Bit;
+use crate::primitive::{Bit, Word};
+use crate::{BitVec, Loc};
+use allocator_api2::alloc::{Allocator, Global};
 use core::iter::FusedIterator;
+#[cfg(feature = "nightly")]
+use core::num::NonZero;
 use core::ops::Range;
 
-impl BitVec {
+impl<A: Allocator> BitVec<A> {
     /// Returns an iterator over the bits of the vector.
     ///
     /// # Examples
@@ -23,25 +26,23 @@ impl BitVec {
     /// ```
     #[inline]
     pub fn iter(&self) -> Iter<'_> {
-        let vec = self;
-        let range = 0..vec.len;
-        Iter { vec, range }
+        Iter::new(&self.buf, 0..self.len)
     }
 }
 
-impl IntoIterator for BitVec {
+impl<A: Allocator> IntoIterator for BitVec<A> {
     type Item = Bit;
-    type IntoIter = IntoIter;
+    type IntoIter = IntoIter<A>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        let cursor = Cursor::new(0..self.len);
         let vec = self;
-        let range = 0..vec.len;
-        IntoIter { vec, range }
+        IntoIter { vec, cursor }
     }
 }
 
-impl<'a> IntoIterator for &'a BitVec {
+impl<'a, A: Allocator> IntoIterator for &'a BitVec<A> {
     type Item = Bit;
     type IntoIter = Iter<'a>;
 
@@ -53,42 +54,101 @@ impl<'a> IntoIterator for &'a BitVec {
 
 /// An owning iterator over the bits of a [`BitVec`].
 #[derive(Debug, Clone)]
-pub struct IntoIter {
-    vec: BitVec,
-    range: Range<usize>,
+pub struct IntoIter<A: Allocator = Global> {
+    vec: BitVec<A>,
+    cursor: Cursor,
 }
 
-impl Iterator for IntoIter {
+impl<A: Allocator> Iterator for IntoIter<A> {
     type Item = Bit;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let index = self.range.next()?;
-        Some(unsafe { self.vec.get_unchecked(index) })
+        unsafe { self.cursor.next(&self.vec.buf) }
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.range.size_hint()
+        let len = self.cursor.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.cursor.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth(&self.vec.buf, n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.fold(&self.vec.buf, init, f) }
     }
 }
 
-impl DoubleEndedIterator for IntoIter {
+impl<A: Allocator> DoubleEndedIterator for IntoIter<A> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        let index = self.range.next_back()?;
-        Some(unsafe { self.vec.get_unchecked(index) })
+        unsafe { self.cursor.next_back(&self.vec.buf) }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth_back(&self.vec.buf, n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_back_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.rfold(&self.vec.buf, init, f) }
     }
 }
 
-impl ExactSizeIterator for IntoIter {}
-impl FusedIterator for IntoIter {}
+impl<A: Allocator> ExactSizeIterator for IntoIter<A> {}
+impl<A: Allocator> FusedIterator for IntoIter<A> {}
 
 /// An iterator over the bits of a [`BitVec`].
 #[derive(Debug, Clone)]
 pub struct Iter<'a> {
-    vec: &'a BitVec,
-    range: Range<usize>,
+    buf: &'a [Word],
+    cursor: Cursor,
+}
+
+impl<'a> Iter<'a> {
+    /// # Notes
+    ///
+    /// The invariant `range.end <= buf.len() * Word::BITS` must hold.
+    pub(crate) fn new(buf: &'a [Word], range: Range<usize>) -> Self {
+        let cursor = Cursor::new(range);
+        Self { buf, cursor }
+    }
 }
 
 impl Iterator for Iter<'_> {
@@ -96,30 +156,241 @@ impl Iterator for Iter<'_> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let index = self.range.next()?;
-        Some(unsafe { self.vec.get_unchecked(index) })
+        unsafe { self.cursor.next(self.buf) }
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.range.size_hint()
+        let len = self.cursor.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.cursor.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth(self.buf, n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.fold(self.buf, init, f) }
     }
 }
 
 impl DoubleEndedIterator for Iter<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        let index = self.range.next_back()?;
-        Some(unsafe { self.vec.get_unchecked(index) })
+        unsafe { self.cursor.next_back(self.buf) }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth_back(self.buf, n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_back_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.rfold(self.buf, init, f) }
     }
 }
 
 impl ExactSizeIterator for Iter<'_> {}
 impl FusedIterator for Iter<'_> {}
 
+/// The iteration state shared by all bit iterators.
+///
+/// Single bits are read straight from their word, which keeps `next` free of
+/// loop-carried state so that the compiler can unroll and vectorize loops over
+/// it. Only `fold` and `rfold` walk the buffer a word at a time, shifting each
+/// word instead of locating every bit from scratch.
+///
+/// # Safety
+///
+/// Every method taking a buffer requires it to be the one the cursor was
+/// created with, and the invariant `range.end <= buf.len() * Word::BITS` must
+/// hold.
+#[derive(Debug, Clone)]
+pub(crate) struct Cursor {
+    range: Range<usize>,
+}
+
+impl Cursor {
+    #[inline]
+    pub(crate) fn new(range: Range<usize>) -> Self {
+        Self { range }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    #[inline]
+    pub(crate) unsafe fn next(&mut self, buf: &[Word]) -> Option<Bit> {
+        let index = self.range.next()?;
+        Some(unsafe { get(buf, index) })
+    }
+
+    #[inline]
+    pub(crate) unsafe fn next_back(&mut self, buf: &[Word]) -> Option<Bit> {
+        let index = self.range.next_back()?;
+        Some(unsafe { get(buf, index) })
+    }
+
+    #[inline]
+    pub(crate) unsafe fn nth(&mut self, buf: &[Word], n: usize) -> Option<Bit> {
+        if self.advance_by(n) != 0 {
+            return None;
+        }
+        unsafe { self.next(buf) }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn nth_back(&mut self, buf: &[Word], n: usize) -> Option<Bit> {
+        if self.advance_back_by(n) != 0 {
+            return None;
+        }
+        unsafe { self.next_back(buf) }
+    }
+
+    /// Skips up to `n` bits from the front, returning how many could not be
+    /// skipped.
+    #[inline]
+    pub(crate) fn advance_by(&mut self, n: usize) -> usize {
+        let step = n.min(self.range.len());
+        self.range.start += step;
+        n - step
+    }
+
+    /// Skips up to `n` bits from the back, returning how many could not be
+    /// skipped.
+    #[inline]
+    pub(crate) fn advance_back_by(&mut self, n: usize) -> usize {
+        let step = n.min(self.range.len());
+        self.range.end -= step;
+        n - step
+    }
+
+    #[inline]
+    pub(crate) unsafe fn fold<B, F>(mut self, buf: &[Word], init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Bit) -> B,
+    {
+        let mut acc = init;
+
+        // Read single bits until the front is word-aligned.
+        while self.range.start % Word::BITS != 0 {
+            match unsafe { self.next(buf) } {
+                None => return acc,
+                Some(value) => acc = f(acc, value),
+            }
+        }
+
+        let Range { start, end } = self.range;
+        let head = unsafe { buf.get_unchecked(start / Word::BITS..end / Word::BITS) };
+        for &word in head {
+            let mut word = word;
+            for _ in 0..Word::BITS {
+                acc = f(acc, word.get(0));
+                word = word << 1;
+            }
+        }
+
+        let used = end % Word::BITS;
+        if used != 0 {
+            let mut word = unsafe { *buf.get_unchecked(end / Word::BITS) };
+            for _ in 0..used {
+                acc = f(acc, word.get(0));
+                word = word << 1;
+            }
+        }
+
+        acc
+    }
+
+    #[inline]
+    pub(crate) unsafe fn rfold<B, F>(mut self, buf: &[Word], init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Bit) -> B,
+    {
+        let mut acc = init;
+
+        // Read single bits until the back is word-aligned.
+        while self.range.end % Word::BITS != 0 {
+            match unsafe { self.next_back(buf) } {
+                None => return acc,
+                Some(value) => acc = f(acc, value),
+            }
+        }
+
+        let Range { start, end } = self.range;
+        let tail = unsafe { buf.get_unchecked(start.div_ceil(Word::BITS)..end / Word::BITS) };
+        for &word in tail.iter().rev() {
+            let mut word = word;
+            for _ in 0..Word::BITS {
+                acc = f(acc, word.get(Word::BITS - 1));
+                word = word >> 1;
+            }
+        }
+
+        let used = Word::BITS - start % Word::BITS;
+        if used != Word::BITS {
+            let mut word = unsafe { *buf.get_unchecked(start / Word::BITS) };
+            for _ in 0..used {
+                acc = f(acc, word.get(Word::BITS - 1));
+                word = word >> 1;
+            }
+        }
+
+        acc
+    }
+}
+
+/// # Safety
+///
+/// The index must lie within `buf`.
+#[inline]
+unsafe fn get(buf: &[Word], index: usize) -> Bit {
+    let loc = Loc::new(index);
+    unsafe { buf.get_unchecked(loc.period) }.get(loc.offset)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::bitvec;
+    use super::*;
+    use crate::{bitvec, boundary_ranges, pattern};
+    use alloc::vec::Vec;
+
+    const LEN: usize = Word::BITS * 3 + 5;
 
     #[test]
     fn test_into_iter() {
@@ -174,4 +445,132 @@ mod tests {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next_back(), None);
     }
+
+    #[test]
+    fn test_iter_ranges() {
+        let (vec, bits) = pattern(LEN);
+
+        for range in boundary_ranges(LEN) {
+            let expected = &bits[range.clone()];
+
+            let iter = Iter::new(&vec.buf, range.clone());
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+
+            let iter = Iter::new(&vec.buf, range.clone());
+            assert_eq!(
+                iter.rev().collect::<Vec<_>>(),
+                expected.iter().rev().copied().collect::<Vec<_>>()
+            );
+
+            let mut iter = Iter::new(&vec.buf, range.clone());
+            let mut expected_iter = expected.iter().copied();
+            loop {
+                let front = iter.next();
+                assert_eq!(front, expected_iter.next());
+                let back = iter.next_back();
+                assert_eq!(back, expected_iter.next_back());
+                if front.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth() {
+        let (vec, bits) = pattern(LEN);
+
+        for range in boundary_ranges(LEN) {
+            let expected = &bits[range.clone()];
+            for n in [0, 1, 2, Word::BITS - 1, Word::BITS, Word::BITS + 3, LEN] {
+                let mut iter = Iter::new(&vec.buf, range.clone());
+                let mut expected_iter = expected.iter().copied();
+                assert_eq!(iter.nth(n), expected_iter.nth(n));
+                assert_eq!(iter.len(), expected_iter.len());
+                assert_eq!(iter.next(), expected_iter.next());
+                assert_eq!(iter.next_back(), expected_iter.next_back());
+
+                let mut iter = Iter::new(&vec.buf, range.clone());
+                let mut expected_iter = expected.iter().copied();
+                assert_eq!(iter.nth_back(n), expected_iter.nth_back(n));
+                assert_eq!(iter.len(), expected_iter.len());
+                assert_eq!(iter.next_back(), expected_iter.next_back());
+                assert_eq!(iter.next(), expected_iter.next());
+            }
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_advance_by() {
+        let (vec, bits) = pattern(LEN);
+
+        for range in boundary_ranges(LEN) {
+            let expected = &bits[range.clone()];
+            for n in [0, 1, Word::BITS - 1, Word::BITS + 3, LEN + 1] {
+                let mut iter = Iter::new(&vec.buf, range.clone());
+                let mut expected_iter = expected.iter().copied();
+                assert_eq!(iter.advance_by(n), expected_iter.advance_by(n));
+                assert_eq!(iter.next(), expected_iter.next());
+                assert_eq!(iter.next_back(), expected_iter.next_back());
+
+                let mut iter = vec.clone().into_iter();
+                let mut expected_iter = bits.iter().copied();
+                assert_eq!(iter.advance_back_by(n), expected_iter.advance_back_by(n));
+                assert_eq!(iter.next_back(), expected_iter.next_back());
+                assert_eq!(iter.next(), expected_iter.next());
+            }
+        }
+    }
+
+    #[test]
+    fn test_fold() {
+        fn push(mut acc: Vec<Bit>, value: Bit) -> Vec<Bit> {
+            acc.push(value);
+            acc
+        }
+
+        let (vec, bits) = pattern(LEN);
+
+        for range in boundary_ranges(LEN) {
+            let expected = &bits[range.clone()];
+
+            let mut iter = Iter::new(&vec.buf, range.clone());
+            let mut expected_iter = expected.iter().copied();
+            iter.next();
+            expected_iter.next();
+            assert_eq!(
+                iter.fold(Vec::new(), push),
+                expected_iter.fold(Vec::new(), push)
+            );
+
+            let mut iter = Iter::new(&vec.buf, range.clone());
+            let mut expected_iter = expected.iter().copied();
+            iter.next_back();
+            expected_iter.next_back();
+            assert_eq!(
+                iter.rfold(Vec::new(), push),
+                expected_iter.rfold(Vec::new(), push)
+            );
+        }
+
+        let expected = bits.iter().filter(|value| **value).count();
+        assert_eq!(vec.iter().filter(|value| *value).count(), expected);
+        assert_eq!(
+            vec.clone().into_iter().filter(|value| *value).count(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_count_last() {
+        let (vec, bits) = pattern(LEN);
+
+        assert_eq!(vec.iter().count(), LEN);
+        assert_eq!(vec.iter().last(), bits.last().copied());
+        assert_eq!(vec.clone().into_iter().count(), LEN);
+        assert_eq!(vec.into_iter().last(), bits.last().copied());
+        assert_eq!(BitVec::new().iter().last(), None);
+    }
 }
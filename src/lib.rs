@@ -27,36 +27,73 @@
 //! ```
 
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(allocator_api, iter_advance_by))]
 
 extern crate alloc;
-
+#[cfg(feature = "std")]
+extern crate std;
+
+pub use self::boxed::BitBox;
+#[cfg(feature = "std")]
+pub use self::error::ReadError;
+pub use self::error::{DecodeError, TryFromBitVecError, TryReserveError, UnderrunError};
+pub use self::field::Integer;
+#[cfg(feature = "std")]
+pub use self::io::ByteReader;
 pub use self::iter::{IntoIter, Iter};
 pub use self::primitive::{Bit, Byte};
+pub use self::runs::Runs;
+pub use self::search::Matches;
+pub use self::slice::BitSlice;
+pub use self::slice::chunks::{Chunks, ChunksExact, IterU64, IterU128, RChunks, Windows};
+pub use self::small::{IntoIter as SmallIntoIter, SmallBitVec};
+pub use self::stream::{BitReader, BitWriter};
 
 use self::primitive::Word;
-use alloc::vec::Vec;
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::Vec;
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::ops::Index;
+use core::ops::{Bound, Index, Range, RangeBounds};
 
+mod arith;
 mod bitwise;
+mod boxed;
 mod convert;
+mod error;
+mod field;
 mod iter;
 mod macros;
 mod primitive;
-
+mod range;
+mod runs;
+mod search;
+mod slice;
+mod small;
+mod stream;
+
+#[cfg(feature = "std")]
+mod io;
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
 
 /// A bit vector.
-#[derive(Default)]
-pub struct BitVec {
+///
+/// Like [`Vec<T, A>`], it is generic over the allocator `A` that owns its
+/// buffer, which defaults to the global allocator. Custom allocators implement
+/// the [`Allocator`] trait of the [`allocator-api2`] crate, or that of the
+/// standard library when the `nightly` feature is enabled.
+///
+/// [`Vec<T, A>`]: alloc::vec::Vec
+/// [`Allocator`]: allocator_api2::alloc::Allocator
+/// [`allocator-api2`]: https://docs.rs/allocator-api2
+pub struct BitVec<A: Allocator = Global> {
     // Invariant: `self.buf_used() <= self.buf.len()`
     len: usize,
-    buf: Vec<Word>,
+    buf: Vec<Word, A>,
 }
 
-impl BitVec {
+impl<A: Allocator> BitVec<A> {
     /// Returns the total number of bits the vector can hold without reallocating.
     ///
     /// # Examples
@@ -68,7 +105,7 @@ impl BitVec {
     /// assert!(vec.capacity() >= 10);
     /// ```
     #[inline]
-    pub const fn capacity(&self) -> usize {
+    pub fn capacity(&self) -> usize {
         self.buf.capacity().saturating_mul(Word::BITS)
     }
 
@@ -154,9 +191,132 @@ impl BitVec {
         let buf = Vec::with_capacity(buf_capacity);
         Self { len, buf }
     }
+
+    /// Creates a new, empty [`BitVec`] with the specified capacity, returning an
+    /// error instead of aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::BitVec;
+    ///
+    /// let vec = BitVec::try_with_capacity(10).unwrap();
+    /// assert_eq!(vec.len(), 0);
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let len = 0;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        let mut buf = Vec::new();
+        buf.try_reserve_exact(buf_capacity)
+            .map_err(|_| reserve_error(buf_capacity))?;
+        Ok(Self { len, buf })
+    }
+
+    /// Creates a new [`BitVec`] of `len` clear bits.
+    fn zeroed(len: usize) -> Self {
+        let mut buf = Vec::new();
+        buf.resize(len.div_ceil(Word::BITS), Word::CLEAR);
+        Self { len, buf }
+    }
+
+    /// Creates a new [`BitVec`] of `len` clear bits, returning an error instead
+    /// of aborting if the allocation fails.
+    #[cfg(feature = "serde")]
+    fn try_zeroed(len: usize) -> Result<Self, TryReserveError> {
+        let mut vec = Self::try_with_capacity(len)?;
+        vec.buf.resize(len.div_ceil(Word::BITS), Word::CLEAR);
+        vec.len = len;
+        Ok(vec)
+    }
 }
 
-impl BitVec {
+impl<A: Allocator> BitVec<A> {
+    /// Creates a new, empty [`BitVec`] in the provided allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// use allocator_api2::alloc::Global;
+    /// use bitvek::BitVec;
+    ///
+    /// let vec = BitVec::new_in(Global);
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 0);
+    /// ```
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        let len = 0;
+        let buf = Vec::new_in(alloc);
+        Self { len, buf }
+    }
+
+    /// Creates a new, empty [`BitVec`] with the specified capacity in the provided
+    /// allocator.
+    ///
+    /// The vector will be able to hold at least `capacity` bits without
+    /// reallocating. This method is allowed to allocate for more bits than
+    /// `capacity`. If `capacity` is zero, the vector will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// use allocator_api2::alloc::Global;
+    /// use bitvek::BitVec;
+    ///
+    /// let vec = BitVec::with_capacity_in(10, Global);
+    /// assert_eq!(vec.len(), 0);
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        let len = 0;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        let buf = Vec::with_capacity_in(buf_capacity, alloc);
+        Self { len, buf }
+    }
+
+    /// Creates a new, empty [`BitVec`] with the specified capacity in the provided
+    /// allocator, returning an error instead of aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// use allocator_api2::alloc::Global;
+    /// use bitvek::BitVec;
+    ///
+    /// let vec = BitVec::try_with_capacity_in(10, Global).unwrap();
+    /// assert_eq!(vec.len(), 0);
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let len = 0;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        let mut buf = Vec::new_in(alloc);
+        buf.try_reserve_exact(buf_capacity)
+            .map_err(|_| reserve_error(buf_capacity))?;
+        Ok(Self { len, buf })
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
     /// Reserves capacity for at least `additional` more bits to be inserted in the
     /// given [`BitVec`]. The collection may reserve more space to speculatively
     /// avoid frequent reallocations. After calling `reserve`, capacity will be
@@ -185,6 +345,43 @@ impl BitVec {
         self
     }
 
+    /// Tries to reserve capacity for at least `additional` more bits to be inserted
+    /// in the given [`BitVec`]. This is the fallible counterpart of
+    /// [`reserve`](BitVec::reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{TryReserveError, bitvec};
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// assert!(vec.try_reserve(6).is_ok());
+    /// assert!(vec.capacity() >= 10);
+    ///
+    /// assert_eq!(
+    ///     vec.try_reserve(usize::MAX).unwrap_err(),
+    ///     TryReserveError::CapacityOverflow,
+    /// );
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<&mut Self, TryReserveError> {
+        let capacity = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        if let Some(buf_additional) = buf_capacity.checked_sub(self.buf.len()) {
+            self.buf
+                .try_reserve(buf_additional)
+                .map_err(|_| reserve_error(buf_capacity))?;
+        };
+        Ok(self)
+    }
+
     /// Shrinks the capacity of the vector as much as possible.
     ///
     /// # Examples
@@ -377,6 +574,64 @@ impl BitVec {
         self
     }
 
+    /// Appends a bit to the back of the vector, returning an error instead of
+    /// panicking or aborting if the vector cannot grow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure. The vector is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.try_push(true).unwrap();
+    /// assert_eq!(vec, bitvec![true, true, false, false, true]);
+    /// ```
+    pub fn try_push(&mut self, value: Bit) -> Result<&mut Self, TryReserveError> {
+        self.try_reserve(1)?;
+        Ok(self.push(value))
+    }
+
+    /// Appends all bits of an iterator to the back of the vector, returning an
+    /// error instead of panicking or aborting if the vector cannot grow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure. Bits appended before the failure are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{TryReserveError, bitvec};
+    /// use std::iter::repeat_n;
+    ///
+    /// let mut vec = bitvec![true, true];
+    /// vec.try_extend([false, false]).unwrap();
+    /// assert_eq!(vec, bitvec![true, true, false, false]);
+    ///
+    /// assert_eq!(
+    ///     vec.try_extend(repeat_n(true, usize::MAX)).unwrap_err(),
+    ///     TryReserveError::CapacityOverflow,
+    /// );
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<&mut Self, TryReserveError>
+    where
+        I: IntoIterator<Item = Bit>,
+    {
+        let iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        self.try_reserve(additional)?;
+        for value in iter {
+            self.try_push(value)?;
+        }
+        Ok(self)
+    }
+
     /// Removes the last bit from the vector and returns it, or `None` if the vector
     /// is empty.
     ///
@@ -402,9 +657,86 @@ impl BitVec {
         let value = word.get(loc.offset);
         Some(value)
     }
+
+    /// Appends the bits yielded by `iter`, assembling them into whole words
+    /// before writing them to the buffer.
+    fn extend_bits<I>(&mut self, iter: I)
+    where
+        I: Iterator<Item = Bit>,
+    {
+        self.reserve(iter.size_hint().0);
+        self.buf.truncate(self.buf_used());
+        // The partial last word stays in the buffer until it is written back, so
+        // the invariant holds with the old length if `iter` panics.
+        let mut offset = Loc::new(self.len).offset;
+        let mut partial = offset != 0;
+        let mut word = if partial {
+            let word = unsafe { self.buf.last().unwrap_unchecked() };
+            *word & !(!Word::CLEAR >> offset)
+        } else {
+            Word::CLEAR
+        };
+        let mut len = self.len - offset;
+        for value in iter {
+            word.set(offset, value);
+            offset += 1;
+            if offset == Word::BITS {
+                len = len.checked_add(Word::BITS).expect("capacity overflow");
+                self.store_word(word, core::mem::take(&mut partial));
+                word = Word::CLEAR;
+                offset = 0;
+            }
+        }
+        if offset != 0 {
+            len = len.checked_add(offset).expect("capacity overflow");
+            self.store_word(word, partial);
+        }
+        self.len = len;
+    }
+
+    /// Overwrites the last word of the buffer with `word` if `partial`, or
+    /// appends it otherwise.
+    #[inline]
+    fn store_word(&mut self, word: Word, partial: bool) {
+        if partial {
+            *unsafe { self.buf.last_mut().unwrap_unchecked() } = word;
+        } else {
+            self.buf.push(word);
+        }
+    }
+
+    /// Appends the first `len` bits of `buf`, shifting whole words into place.
+    fn extend_words(&mut self, buf: &[Word], len: usize) {
+        let new_len = self.len.checked_add(len).expect("capacity overflow");
+        let words = unsafe { buf.get_unchecked(0..len.div_ceil(Word::BITS)) };
+        self.buf.truncate(self.buf_used());
+        self.buf
+            .reserve(new_len.div_ceil(Word::BITS) - self.buf.len());
+        let shift = self.len % Word::BITS;
+        if shift == 0 {
+            self.buf.extend_from_slice(words);
+        } else {
+            let last = unsafe { self.buf.last_mut().unwrap_unchecked() };
+            *last &= !(!Word::CLEAR >> shift);
+            for &word in words {
+                let last = unsafe { self.buf.last_mut().unwrap_unchecked() };
+                *last |= word >> shift;
+                self.buf.push(word << (Word::BITS - shift));
+            }
+        }
+        self.len = new_len;
+        self.buf.truncate(self.buf_used());
+    }
 }
 
-impl Index<usize> for BitVec {
+impl Default for BitVec {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Allocator> Index<usize> for BitVec<A> {
     type Output = Bit;
 
     #[inline]
@@ -417,84 +749,184 @@ impl Index<usize> for BitVec {
     }
 }
 
-impl Clone for BitVec {
+impl<A: Allocator + Clone> Clone for BitVec<A> {
     fn clone(&self) -> Self {
         let len = self.len;
         let buf_len = self.buf_used();
-        let buf = unsafe { self.buf.get_unchecked(0..buf_len).to_vec() };
+        let alloc = self.buf.allocator().clone();
+        let mut buf = Vec::with_capacity_in(buf_len, alloc);
+        buf.extend_from_slice(unsafe { self.buf.get_unchecked(0..buf_len) });
         Self { len, buf }
     }
 }
 
-impl fmt::Debug for BitVec {
+impl<A: Allocator> fmt::Debug for BitVec<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
-impl Extend<Bit> for BitVec {
+impl<A: Allocator> Extend<Bit> for BitVec<A> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = Bit>,
     {
-        let iter = iter.into_iter();
-        let additional = iter.size_hint().0;
-        self.reserve(additional);
-        for value in iter {
-            self.push(value);
+        self.extend_bits(iter.into_iter());
+    }
+}
+
+impl<'a, A: Allocator> Extend<&'a Bit> for BitVec<A> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a Bit>,
+    {
+        self.extend_bits(iter.into_iter().copied());
+    }
+}
+
+impl<A: Allocator, B: Allocator> Extend<BitVec<B>> for BitVec<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = BitVec<B>>,
+    {
+        for vec in iter {
+            self.extend_words(&vec.buf, vec.len);
         }
     }
 }
 
-impl Hash for BitVec {
+impl<A: Allocator> Hash for BitVec<A> {
+    #[inline]
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
-        if self.is_empty() {
-            return;
-        }
+        unsafe { hash_bits(self.len, &self.buf, state) };
+    }
+}
 
-        self.len.hash(state);
+impl<A: Allocator, B: Allocator> PartialEq<BitVec<B>> for BitVec<A> {
+    #[inline]
+    fn eq(&self, other: &BitVec<B>) -> bool {
+        self.len == other.len && unsafe { eq_bits(self.len, &self.buf, &other.buf) }
+    }
+}
 
-        let last = self.len - 1;
-        let loc = Loc::new(last);
+impl<A: Allocator> Eq for BitVec<A> {}
+
+/// Hashes the first `len` bits of `buf`, ignoring any bits beyond.
+///
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+unsafe fn hash_bits<H>(len: usize, buf: &[Word], state: &mut H)
+where
+    H: Hasher,
+{
+    debug_assert!(len <= buf.len() * Word::BITS);
+    if len == 0 {
+        return;
+    }
 
-        let head = unsafe { self.buf.get_unchecked(..loc.period) };
-        head.hash(state);
+    len.hash(state);
 
-        let tail = unsafe { self.buf.get_unchecked(loc.period) };
-        tail.align_last_to_lsb(loc.offset).hash(state);
-    }
+    let last = len - 1;
+    let loc = Loc::new(last);
+
+    let head = unsafe { buf.get_unchecked(..loc.period) };
+    head.hash(state);
+
+    let tail = unsafe { buf.get_unchecked(loc.period) };
+    tail.align_last_to_lsb(loc.offset).hash(state);
 }
 
-impl PartialEq for BitVec {
-    fn eq(&self, other: &Self) -> bool {
-        if self.len != other.len {
-            return false;
-        }
+/// Compares the first `len` bits of `lhs` and `rhs`, ignoring any bits beyond.
+///
+/// # Safety
+///
+/// Both buffers must hold at least `len` bits.
+unsafe fn eq_bits(len: usize, lhs: &[Word], rhs: &[Word]) -> bool {
+    debug_assert!(len <= lhs.len() * Word::BITS && len <= rhs.len() * Word::BITS);
+    if len == 0 {
+        return true;
+    }
 
-        if self.is_empty() {
-            return true;
-        }
+    let last = len - 1;
+    let loc = Loc::new(last);
 
-        let last = self.len - 1;
-        let loc = Loc::new(last);
+    let lhs_head = unsafe { lhs.get_unchecked(..loc.period) };
+    let rhs_head = unsafe { rhs.get_unchecked(..loc.period) };
+    if lhs_head != rhs_head {
+        return false;
+    }
 
-        let lhs_head = unsafe { self.buf.get_unchecked(..loc.period) };
-        let rhs_head = unsafe { other.buf.get_unchecked(..loc.period) };
-        if lhs_head != rhs_head {
-            return false;
-        }
+    let lhs_tail = unsafe { lhs.get_unchecked(loc.period) };
+    let rhs_tail = unsafe { rhs.get_unchecked(loc.period) };
+    (*lhs_tail ^ *rhs_tail).align_last_to_lsb(loc.offset) == Word::CLEAR
+}
 
-        let lhs_tail = unsafe { self.buf.get_unchecked(loc.period) };
-        let rhs_tail = unsafe { other.buf.get_unchecked(loc.period) };
-        (*lhs_tail ^ *rhs_tail).align_last_to_lsb(loc.offset) == Word::CLEAR
+/// Classifies a failure to reserve a buffer of `buf_capacity` words, which is a
+/// capacity overflow if the buffer would exceed `isize::MAX` bytes and an
+/// allocator failure otherwise.
+fn reserve_error(buf_capacity: usize) -> TryReserveError {
+    if buf_capacity > isize::MAX as usize / Word::BYTES {
+        TryReserveError::CapacityOverflow
+    } else {
+        TryReserveError::AllocError
     }
 }
 
-impl Eq for BitVec {}
+/// Resolves `range` against a sequence of `len` bits, panicking with the same
+/// messages as slice indexing if it is out of bounds.
+fn resolve_range<R>(range: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    start..end
+}
+
+/// Resolves `range` against a sequence of `len` bits, returning `None` if it is
+/// out of bounds.
+fn checked_range<R>(range: R, len: usize) -> Option<Range<usize>>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some(start..end)
+}
 
 #[derive(Debug)]
 struct Loc {
@@ -517,13 +949,64 @@ impl BitVec {
     }
 }
 
+/// Returns a vector of `len` irregular bits with dirty padding and an unused
+/// word, together with the same bits as a plain `Vec`.
+#[cfg(test)]
+fn pattern(len: usize) -> (BitVec, alloc::vec::Vec<Bit>) {
+    let bits: alloc::vec::Vec<Bit> = (0..len)
+        .map(|index| index % 3 == 0 || index % 7 == 0)
+        .collect();
+    let mut vec = BitVec::from(&bits[..]);
+    vec.push(true).pop();
+    vec.push_unused_word();
+    (vec, bits)
+}
+
+/// Returns every range within `0..len` whose ends lie on or next to a word
+/// boundary.
+#[cfg(test)]
+fn boundary_ranges(len: usize) -> impl Iterator<Item = Range<usize>> {
+    let points = [
+        0,
+        1,
+        Word::BITS - 1,
+        Word::BITS,
+        Word::BITS + 1,
+        Word::BITS * 2 + 3,
+        len - 1,
+        len,
+    ];
+    points
+        .into_iter()
+        .flat_map(move |start| points.into_iter().map(move |end| start..end))
+        .filter(move |range| range.start <= range.end && range.end <= len)
+}
+
 #[cfg(test)]
 mod tests {
     extern crate std;
 
     use super::*;
+    use allocator_api2::alloc::{AllocError, Layout};
+    use core::cell::Cell;
     use core::iter::repeat_n;
+    use core::ptr::NonNull;
     use std::hash::DefaultHasher;
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, Default)]
+    struct CountingAllocator(Rc<Cell<usize>>);
+
+    unsafe impl Allocator for CountingAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
 
     #[test]
     fn test_capacity() {
@@ -580,6 +1063,43 @@ mod tests {
         assert!(vec.buf.capacity() >= 2);
     }
 
+    #[test]
+    fn test_new_in() {
+        let alloc = CountingAllocator::default();
+
+        let mut vec = BitVec::new_in(alloc.clone());
+        assert_eq!(vec.capacity(), 0);
+        assert_eq!(alloc.0.get(), 0);
+
+        vec.push(true).push(false);
+        assert_eq!(alloc.0.get(), 1);
+
+        let cloned = vec.clone();
+        assert_eq!(alloc.0.get(), 2);
+        assert_eq!(cloned, bitvec![true, false]);
+
+        let negated = !&vec;
+        assert_eq!(alloc.0.get(), 3);
+        assert_eq!(negated, bitvec![false, true]);
+
+        let boxed = vec.into_boxed_bitslice();
+        assert_eq!(boxed, bitvec![true, false].into_boxed_bitslice());
+    }
+
+    #[test]
+    fn test_with_capacity_in() {
+        let alloc = CountingAllocator::default();
+
+        let mut vec = BitVec::with_capacity_in(Word::BITS, alloc.clone());
+        assert_eq!(alloc.0.get(), 1);
+        vec.extend([true; Word::BITS]);
+        assert_eq!(alloc.0.get(), 1);
+
+        let vec = BitVec::try_with_capacity_in(Word::BITS, alloc.clone()).unwrap();
+        assert!(vec.capacity() >= Word::BITS);
+        assert_eq!(alloc.0.get(), 2);
+    }
+
     #[test]
     fn test_reserve() {
         let mut vec = bitvec![true, true, false, false];
@@ -594,6 +1114,41 @@ mod tests {
         assert!(vec.capacity() >= Word::BITS * 2);
     }
 
+    #[test]
+    fn test_try_with_capacity() {
+        let vec = BitVec::try_with_capacity(Word::BITS + 1).unwrap();
+        assert_eq!(vec.len, 0);
+        assert!(vec.buf.capacity() >= 2);
+
+        let result = BitVec::try_with_capacity(usize::MAX);
+        assert_eq!(result.unwrap_err(), TryReserveError::AllocError);
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        let mut vec = bitvec![true, true, false, false];
+        let unchanged = vec.clone();
+
+        assert!(vec.try_reserve(Word::BITS).is_ok());
+        assert!(vec.capacity() >= Word::BITS * 2);
+
+        let result = vec.try_reserve(usize::MAX);
+        assert_eq!(result.unwrap_err(), TryReserveError::CapacityOverflow);
+
+        let result = vec.try_reserve(usize::MAX - vec.len);
+        assert_eq!(result.unwrap_err(), TryReserveError::AllocError);
+        assert_eq!(vec, unchanged);
+    }
+
+    #[test]
+    fn test_reserve_error() {
+        let max = isize::MAX as usize / Word::BYTES;
+        assert_eq!(reserve_error(0), TryReserveError::AllocError);
+        assert_eq!(reserve_error(max), TryReserveError::AllocError);
+        assert_eq!(reserve_error(max + 1), TryReserveError::CapacityOverflow);
+        assert_eq!(reserve_error(usize::MAX), TryReserveError::CapacityOverflow);
+    }
+
     #[test]
     fn test_shrink_to_fit() {
         let mut vec = bitvec![true, true, false, false];
@@ -857,6 +1412,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_push() {
+        let mut vec = bitvec![true; Word::BITS];
+        vec.try_push(false).unwrap();
+        assert_eq!(vec.len, Word::BITS + 1);
+        assert_eq!(vec.get(Word::BITS), Some(false));
+
+        let mut vec = BitVec {
+            len: usize::MAX,
+            buf: Vec::new(),
+        };
+        let result = vec.try_push(true);
+        assert_eq!(result.unwrap_err(), TryReserveError::CapacityOverflow);
+    }
+
     #[test]
     fn test_pop() {
         {
@@ -977,6 +1547,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extend_unwind() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        for prefix in [3, Word::BITS - 1, Word::BITS + 3] {
+            let mut vec = BitVec::from_iter(repeat_n(true, prefix));
+            let bits = (0..Word::BITS + 2).map(|index| {
+                assert!(index != 1 && index != Word::BITS + 1, "iterator panicked");
+                index % 2 == 0
+            });
+            let result = catch_unwind(AssertUnwindSafe(|| vec.extend(bits)));
+            assert!(result.is_err());
+            assert!(vec.buf_used() <= vec.buf.len());
+            assert_eq!(vec.len, prefix);
+            for index in 0..prefix {
+                assert_eq!(vec.get(index), Some(true));
+            }
+            vec.push(false);
+            assert_eq!(vec.get(prefix), Some(false));
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_extend_fail() {
@@ -985,6 +1577,66 @@ mod tests {
         vec.extend(repeat_n(true, usize::MAX));
     }
 
+    #[test]
+    fn test_extend_ref() {
+        let bits: [Bit; Word::BITS + 3] = core::array::from_fn(|index| index % 3 == 0);
+        for prefix in [0, 1, Word::BITS - 1, Word::BITS] {
+            let mut vec = BitVec::from_iter(repeat_n(true, prefix));
+            vec.push_unused_word();
+            vec.extend(&bits);
+            assert_eq!(vec.len, prefix + bits.len());
+            assert_eq!(vec.buf.len(), vec.buf_used());
+            for index in 0..prefix {
+                assert_eq!(vec.get(index), Some(true));
+            }
+            for (index, value) in bits.iter().enumerate() {
+                assert_eq!(vec.get(prefix + index), Some(*value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_extend_vec() {
+        let bits: [Bit; Word::BITS * 2 + 3] = core::array::from_fn(|index| index % 3 == 0);
+        for prefix in [0, 1, Word::BITS - 1, Word::BITS, Word::BITS + 1] {
+            for count in [0, 1, Word::BITS, bits.len()] {
+                let mut vec = BitVec::from_iter(repeat_n(true, prefix));
+                vec.push(true).pop();
+                vec.push_unused_word();
+                let mut other = BitVec::from(&bits[..count]);
+                other.push(true).pop();
+                other.push_unused_word();
+
+                vec.extend([other.clone(), other]);
+                assert_eq!(vec.len, prefix + count * 2);
+                assert_eq!(vec.buf.len(), vec.buf_used());
+                for index in 0..prefix {
+                    assert_eq!(vec.get(index), Some(true));
+                }
+                for index in 0..count * 2 {
+                    assert_eq!(vec.get(prefix + index), Some(bits[index % count]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_extend() {
+        let mut vec = bitvec![true, true, false, false];
+        let unchanged = vec.clone();
+
+        vec.try_extend([true; Word::BITS]).unwrap();
+        assert_eq!(vec.len, Word::BITS + 4);
+        for index in 4..vec.len {
+            assert_eq!(vec.get(index), Some(true));
+        }
+
+        let mut vec = unchanged;
+        let result = vec.try_extend(repeat_n(true, usize::MAX));
+        assert_eq!(result.unwrap_err(), TryReserveError::CapacityOverflow);
+        assert_eq!(vec.len, 4);
+    }
+
     #[test]
     fn test_hash() {
         fn hash(vec: &BitVec) -> u64 {
@@ -33,7 +33,7 @@ impl Word {
         Self(usize::from_be_bytes(value))
     }
 
-    #[cfg(feature = "serde")]
+    #[cfg(any(feature = "serde", feature = "std"))]
     pub(crate) fn to_byte_array(self) -> [Byte; Word::BYTES] {
         self.0.to_be_bytes()
     }
@@ -63,6 +63,52 @@ impl Word {
         }
     }
 
+    /// Returns `count` bits starting at `index`, aligned to the LSB.
+    ///
+    /// # Notes
+    ///
+    /// Overflows if `count == 0` or `index + count > Word::BITS`.
+    pub(crate) fn extract(self, index: usize, count: usize) -> usize {
+        (self.0 << index) >> (Self::BITS - count)
+    }
+
+    /// Replaces `count` bits starting at `index` with the lowest `count` bits of
+    /// `value`.
+    ///
+    /// # Notes
+    ///
+    /// Overflows if `count == 0` or `index + count > Word::BITS`.
+    pub(crate) fn insert(&mut self, index: usize, count: usize, value: usize) {
+        let shift = Self::BITS - index - count;
+        let mask = Self::span(index, count);
+        *self = *self & !mask | Self(value << shift) & mask;
+    }
+
+    pub(crate) fn leading_zeros(self) -> usize {
+        self.0.leading_zeros() as usize
+    }
+
+    pub(crate) fn trailing_zeros(self) -> usize {
+        self.0.trailing_zeros() as usize
+    }
+
+    pub(crate) fn count_ones(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub(crate) fn reverse_bits(self) -> Self {
+        Self(self.0.reverse_bits())
+    }
+
+    /// Returns a word with `count` bits set starting at `index`.
+    ///
+    /// # Notes
+    ///
+    /// Overflows if `count == 0` or `index + count > Word::BITS`.
+    pub(crate) fn span(index: usize, count: usize) -> Self {
+        Self((usize::MAX >> (Self::BITS - count)) << (Self::BITS - index - count))
+    }
+
     /// # Notes
     ///
     /// Overflows if `index >= Word::BITS`.
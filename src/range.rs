@@ -0,0 +1,414 @@
+use crate::primitive::{Bit, Word};
+use crate::slice::{load_bits, store_bits};
+use crate::{BitVec, Loc, checked_range, resolve_range};
+use allocator_api2::alloc::Allocator;
+use core::ops::{Range, RangeBounds};
+
+impl<A: Allocator> BitVec<A> {
+    /// Sets all bits of the vector to the specified value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.fill(true);
+    /// assert_eq!(vec, bitvec![true; 4]);
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: Bit) -> &mut Self {
+        self.fill_range(.., value)
+    }
+
+    /// Sets the bits in the specified range to the specified value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.fill_range(1..3, false);
+    /// assert_eq!(vec, bitvec![true, false, false, false]);
+    /// ```
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { self.fill_range_unchecked(range, value) }
+    }
+
+    /// Sets the bits in the specified range to the specified value, if in
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// assert!(vec.set_range(2.., true).is_some());
+    /// assert!(vec.set_range(3..5, false).is_none());
+    /// assert_eq!(vec, bitvec![true; 4]);
+    /// ```
+    #[must_use]
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> Option<&mut Self> {
+        let range = checked_range(range, self.len)?;
+        Some(unsafe { self.fill_range_unchecked(range, value) })
+    }
+
+    /// Flips the bits in the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.toggle_range(1..3);
+    /// assert_eq!(vec, bitvec![true, false, true, false]);
+    /// ```
+    pub fn toggle_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { toggle_range(&mut self.buf, range) };
+        self
+    }
+
+    /// Copies the bits in `src` to the position starting at `dest`. The two
+    /// ranges may overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds, or if the destination range ends past
+    /// the end of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false, false];
+    /// vec.copy_within(0..3, 2);
+    /// assert_eq!(vec, bitvec![true, true, true, true, false]);
+    /// ```
+    pub fn copy_within(&mut self, src: impl RangeBounds<usize>, dest: usize) -> &mut Self {
+        let src = resolve_range(src, self.len);
+        assert!(dest <= self.len - src.len(), "dest is out of bounds");
+        unsafe { copy_within(&mut self.buf, src, dest) };
+        self
+    }
+
+    /// Swaps two bits in the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, false];
+    /// vec.swap(0, 3);
+    /// assert_eq!(vec, bitvec![false, true, false, true]);
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) -> &mut Self {
+        assert!(a < self.len && b < self.len, "index out of bounds");
+        unsafe { swap(&mut self.buf, a, b) };
+        self
+    }
+
+    /// Reverses the order of bits in the vector, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, true, false];
+    /// vec.reverse();
+    /// assert_eq!(vec, bitvec![false, true, false, true, true]);
+    /// ```
+    pub fn reverse(&mut self) -> &mut Self {
+        unsafe { reverse(&mut self.buf, self.len) };
+        self
+    }
+
+    /// Reverses the order of bits in the specified range, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let mut vec = bitvec![true, true, false, true, false];
+    /// vec.reverse_range(1..4);
+    /// assert_eq!(vec, bitvec![true, true, false, true, false]);
+    /// vec.reverse_range(..3);
+    /// assert_eq!(vec, bitvec![false, true, true, true, false]);
+    /// ```
+    pub fn reverse_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { reverse_range(&mut self.buf, range) };
+        self
+    }
+
+    /// # Safety
+    ///
+    /// The range must lie within `0..self.len`.
+    pub(crate) unsafe fn fill_range_unchecked(
+        &mut self,
+        range: Range<usize>,
+        value: Bit,
+    ) -> &mut Self {
+        unsafe { fill_range(&mut self.buf, range, value) };
+        self
+    }
+}
+
+/// # Safety
+///
+/// The range must lie within `buf`.
+pub(crate) unsafe fn fill_range(buf: &mut [Word], range: Range<usize>, value: Bit) {
+    let fill = if value { !Word::CLEAR } else { Word::CLEAR };
+    unsafe {
+        for_each_span(buf, range, |word, mask| {
+            *word = *word & !mask | fill & mask;
+        });
+    }
+}
+
+/// # Safety
+///
+/// The range must lie within `buf`.
+pub(crate) unsafe fn toggle_range(buf: &mut [Word], range: Range<usize>) {
+    unsafe { for_each_span(buf, range, |word, mask| *word ^= mask) };
+}
+
+/// # Safety
+///
+/// Both `src` and the range of the same length starting at `dest` must lie
+/// within `buf`.
+pub(crate) unsafe fn copy_within(buf: &mut [Word], src: Range<usize>, dest: usize) {
+    let count = src.len();
+    let chunk = |offset: usize| {
+        let len = Word::BITS.min(count - offset);
+        (src.start + offset..src.start + offset + len, dest + offset)
+    };
+    let mut copy = |(from, to): (Range<usize>, usize)| unsafe {
+        let bits = load_bits(buf, from.clone());
+        store_bits(buf, to..to + from.len(), bits);
+    };
+    if dest <= src.start {
+        (0..count)
+            .step_by(Word::BITS)
+            .map(chunk)
+            .for_each(&mut copy);
+    } else {
+        (0..count)
+            .step_by(Word::BITS)
+            .rev()
+            .map(chunk)
+            .for_each(&mut copy);
+    }
+}
+
+/// # Safety
+///
+/// Both `a` and `b` must lie within `buf`.
+pub(crate) unsafe fn swap(buf: &mut [Word], a: usize, b: usize) {
+    let (a, b) = (Loc::new(a), Loc::new(b));
+    let left = unsafe { buf.get_unchecked(a.period) }.get(a.offset);
+    let right = unsafe { buf.get_unchecked(b.period) }.get(b.offset);
+    if left != right {
+        unsafe {
+            buf.get_unchecked_mut(a.period).set(a.offset, right);
+            buf.get_unchecked_mut(b.period).set(b.offset, left);
+        }
+    }
+}
+
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+pub(crate) unsafe fn reverse(buf: &mut [Word], len: usize) {
+    let buf_len = len.div_ceil(Word::BITS);
+    let words = unsafe { buf.get_unchecked_mut(..buf_len) };
+    words.reverse();
+    words
+        .iter_mut()
+        .for_each(|word| *word = word.reverse_bits());
+
+    // The bits beyond `len` in the last word are now at the front, so shift
+    // the whole buffer left to realign the first bit.
+    let shift = buf_len * Word::BITS - len;
+    if shift != 0 {
+        for index in 0..buf_len {
+            let next = words.get(index + 1).copied().unwrap_or(Word::CLEAR);
+            words[index] = words[index] << shift | next >> (Word::BITS - shift);
+        }
+    }
+}
+
+/// # Safety
+///
+/// The range must lie within `buf`.
+pub(crate) unsafe fn reverse_range(buf: &mut [Word], range: Range<usize>) {
+    let Range { mut start, mut end } = range;
+
+    // Swap whole words from both ends, reversing each of them.
+    while end - start >= Word::BITS * 2 {
+        let front = start..start + Word::BITS;
+        let back = end - Word::BITS..end;
+        unsafe {
+            let front_bits = load_bits(buf, front.clone()) as usize;
+            let back_bits = load_bits(buf, back.clone()) as usize;
+            store_bits(buf, front, back_bits.reverse_bits() as u128);
+            store_bits(buf, back, front_bits.reverse_bits() as u128);
+        }
+        start += Word::BITS;
+        end -= Word::BITS;
+    }
+
+    // Fewer than two words remain in the middle, which fit in a `u128`.
+    let count = end - start;
+    if count != 0 {
+        unsafe {
+            let bits = load_bits(buf, start..end);
+            let reversed = bits.reverse_bits() >> (u128::BITS as usize - count);
+            store_bits(buf, start..end, reversed);
+        }
+    }
+}
+
+/// Calls `f` with each word overlapping `range` and a mask of the bits of that
+/// word that lie within the range.
+///
+/// # Safety
+///
+/// The range must lie within `buf`.
+unsafe fn for_each_span<F>(buf: &mut [Word], range: Range<usize>, mut f: F)
+where
+    F: FnMut(&mut Word, Word),
+{
+    let mut index = range.start;
+    while index < range.end {
+        let loc = Loc::new(index);
+        let count = (Word::BITS - loc.offset).min(range.end - index);
+        let word = unsafe { buf.get_unchecked_mut(loc.period) };
+        f(word, Word::span(loc.offset, count));
+        index += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitvec, boundary_ranges, pattern};
+
+    const LEN: usize = Word::BITS * 3 + 5;
+
+    #[test]
+    fn test_fill_range() {
+        for range in boundary_ranges(LEN) {
+            for value in [false, true] {
+                let (mut vec, mut bits) = pattern(LEN);
+                vec.fill_range(range.clone(), value);
+                bits[range.clone()].fill(value);
+                assert!(vec.iter().eq(bits.iter().copied()));
+
+                let (mut vec, _) = pattern(LEN);
+                assert!(vec.set_range(range.clone(), value).is_some());
+                assert!(vec.iter().eq(bits.iter().copied()));
+            }
+
+            let (mut vec, mut bits) = pattern(LEN);
+            vec.toggle_range(range.clone());
+            bits[range.clone()].iter_mut().for_each(|bit| *bit = !*bit);
+            assert!(vec.iter().eq(bits.iter().copied()));
+        }
+
+        let (mut vec, _) = pattern(LEN);
+        assert_eq!(vec.fill(true).clone(), bitvec![true; LEN]);
+        assert!(vec.set_range(LEN..LEN + 1, false).is_none());
+        assert!(vec.set_range(..=usize::MAX, false).is_none());
+        assert_eq!(vec, bitvec![true; LEN]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 6 out of range for slice of length 5")]
+    fn test_fill_range_fail() {
+        bitvec![true; 5].fill_range(2..6, false);
+    }
+
+    #[test]
+    fn test_copy_within() {
+        for src in boundary_ranges(LEN) {
+            for dest in [0, 1, Word::BITS - 1, Word::BITS + 2, LEN - src.len()] {
+                if dest + src.len() > LEN {
+                    continue;
+                }
+                let (mut vec, mut bits) = pattern(LEN);
+                vec.copy_within(src.clone(), dest);
+                bits.copy_within(src.clone(), dest);
+                assert!(vec.iter().eq(bits.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let (mut vec, mut bits) = pattern(LEN);
+        for (a, b) in [(0, 1), (0, LEN - 1), (Word::BITS, 2), (5, 5)] {
+            vec.swap(a, b);
+            bits.swap(a, b);
+            assert!(vec.iter().eq(bits.iter().copied()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_swap_fail() {
+        bitvec![true; 5].swap(1, 5);
+    }
+
+    #[test]
+    fn test_reverse() {
+        for len in [0, 1, 7, Word::BITS, Word::BITS + 1, LEN] {
+            let (mut vec, mut bits) = pattern(LEN);
+            vec.buf.truncate(len.div_ceil(Word::BITS));
+            vec.len = len;
+            vec.push_unused_word();
+            bits.truncate(len);
+
+            vec.reverse();
+            bits.reverse();
+            assert!(vec.iter().eq(bits.iter().copied()));
+        }
+
+        for range in boundary_ranges(LEN) {
+            let (mut vec, mut bits) = pattern(LEN);
+            vec.reverse_range(range.clone());
+            bits[range.clone()].reverse();
+            assert!(vec.iter().eq(bits.iter().copied()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dest is out of bounds")]
+    fn test_copy_within_fail() {
+        bitvec![true; 5].copy_within(1..3, 4);
+    }
+}
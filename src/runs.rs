@@ -0,0 +1,213 @@
+use crate::primitive::{Bit, Word};
+use crate::{BitVec, Loc};
+use allocator_api2::alloc::Allocator;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+impl BitVec {
+    /// Creates a [`BitVec`] from `(bit, len)` pairs, each appending `len` copies
+    /// of `bit`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length exceeds `usize::MAX` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, bitvec};
+    ///
+    /// let vec = BitVec::from_runs([(true, 2), (false, 3), (true, 1)]);
+    /// assert_eq!(vec, bitvec![true, true, false, false, false, true]);
+    ///
+    /// let runs = vec.iter_runs().map(|(bit, _, len)| (bit, len));
+    /// assert_eq!(BitVec::from_runs(runs), vec);
+    /// ```
+    pub fn from_runs<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Bit, usize)>,
+    {
+        let mut vec = BitVec::new();
+        for (value, count) in iter {
+            let start = vec.len;
+            let len = start.checked_add(count).expect("capacity overflow");
+            vec.buf.resize(len.div_ceil(Word::BITS), Word::CLEAR);
+            vec.len = len;
+            unsafe { vec.fill_range_unchecked(start..len, value) };
+        }
+        vec
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns an iterator over the maximal runs of equal bits in the vector,
+    /// yielding `(bit, start, len)` for each run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false, false, true];
+    /// let runs: Vec<_> = vec.iter_runs().collect();
+    /// assert_eq!(runs, [(true, 0, 2), (false, 2, 3), (true, 5, 1)]);
+    /// ```
+    #[inline]
+    pub fn iter_runs(&self) -> Runs<'_> {
+        Runs::new(&self.buf, 0..self.len)
+    }
+}
+
+/// An iterator over the maximal runs of equal bits in a [`BitVec`] or a
+/// [`SmallBitVec`](crate::SmallBitVec).
+///
+/// This struct is created by [`BitVec::iter_runs`] and
+/// [`SmallBitVec::iter_runs`](crate::SmallBitVec::iter_runs).
+#[derive(Debug, Clone)]
+pub struct Runs<'a> {
+    buf: &'a [Word],
+    range: Range<usize>,
+}
+
+impl<'a> Runs<'a> {
+    pub(crate) fn new(buf: &'a [Word], range: Range<usize>) -> Self {
+        Self { buf, range }
+    }
+}
+
+impl Iterator for Runs<'_> {
+    type Item = (Bit, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let start = self.range.start;
+        let loc = Loc::new(start);
+        let value = unsafe { self.buf.get_unchecked(loc.period) }.get(loc.offset);
+
+        // Invert the words of a run of ones, so that the run always ends at the
+        // next set bit.
+        let mut index = start;
+        while index < self.range.end {
+            let loc = Loc::new(index);
+            let word = unsafe { *self.buf.get_unchecked(loc.period) };
+            let word = if value { !word } else { word };
+            let available = Word::BITS - loc.offset;
+            let count = (word << loc.offset).leading_zeros().min(available);
+            index += count;
+            if count < available {
+                break;
+            }
+        }
+
+        let end = index.min(self.range.end);
+        self.range.start = end;
+        Some((value, start, end - start))
+    }
+}
+
+impl DoubleEndedIterator for Runs<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let end = self.range.end;
+        let loc = Loc::new(end - 1);
+        let value = unsafe { self.buf.get_unchecked(loc.period) }.get(loc.offset);
+
+        // Scan backwards, counting the trailing bits equal to `value` below each
+        // position.
+        let mut index = end;
+        while index > self.range.start {
+            let loc = Loc::new(index - 1);
+            let word = unsafe { *self.buf.get_unchecked(loc.period) };
+            let word = if value { !word } else { word };
+            let available = loc.offset + 1;
+            let count = (word >> (Word::BITS - available))
+                .trailing_zeros()
+                .min(available);
+            index -= count;
+            if count < available {
+                break;
+            }
+        }
+
+        let start = index.max(self.range.start);
+        self.range.end = start;
+        Some((value, start, end - start))
+    }
+}
+
+impl FusedIterator for Runs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn naive(bits: &[Bit]) -> Vec<(Bit, usize, usize)> {
+        let mut runs: Vec<(Bit, usize, usize)> = Vec::new();
+        for (index, &value) in bits.iter().enumerate() {
+            match runs.last_mut() {
+                Some((last, _, len)) if *last == value => *len += 1,
+                _ => runs.push((value, index, 1)),
+            }
+        }
+        runs
+    }
+
+    #[test]
+    fn test_iter_runs() {
+        let patterns: [fn(usize) -> Bit; 4] = [
+            |index| index % 7 < 3,
+            |index| index < Word::BITS + 3,
+            |index| index >= Word::BITS * 2 || index == 5,
+            |_| true,
+        ];
+        for pattern in patterns {
+            for len in [0, 1, Word::BITS, Word::BITS * 3 + 5] {
+                let bits: Vec<Bit> = (0..len).map(pattern).collect();
+                let mut vec = BitVec::from(&bits[..]);
+                vec.push(!bits.last().copied().unwrap_or_default()).pop();
+                vec.push_unused_word();
+
+                let expected = naive(&bits);
+                assert_eq!(vec.iter_runs().collect::<Vec<_>>(), expected);
+                assert_eq!(
+                    vec.iter_runs().rev().collect::<Vec<_>>(),
+                    expected.iter().rev().copied().collect::<Vec<_>>()
+                );
+
+                let mut iter = vec.iter_runs();
+                let mut expected = expected.into_iter();
+                loop {
+                    let front = iter.next();
+                    assert_eq!(front, expected.next());
+                    assert_eq!(iter.next_back(), expected.next_back());
+                    if front.is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_runs() {
+        let runs = [
+            (true, 3),
+            (false, Word::BITS + 2),
+            (true, 0),
+            (true, Word::BITS * 2),
+        ];
+        let vec = BitVec::from_runs(runs);
+        let bits: Vec<Bit> = runs
+            .iter()
+            .flat_map(|&(value, len)| core::iter::repeat_n(value, len))
+            .collect();
+        assert_eq!(vec, BitVec::from(&bits[..]));
+        assert_eq!(vec.buf.len(), vec.buf_used());
+        assert_eq!(BitVec::from_runs([]), BitVec::new());
+    }
+}
@@ -0,0 +1,464 @@
+use crate::primitive::Word;
+use crate::slice::load_bits;
+use crate::{BitVec, Loc};
+use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns the index of the first occurrence of `pattern` in the vector, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, false, true, true, false, true, true];
+    /// assert_eq!(vec.find(&bitvec![true, true]), Some(2));
+    /// assert_eq!(vec.find(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn find<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { find(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns the index of the last occurrence of `pattern` in the vector, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at the end of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, false, true, true, false, true, true];
+    /// assert_eq!(vec.rfind(&bitvec![true, true]), Some(5));
+    /// assert_eq!(vec.rfind(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn rfind<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { rfind(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns an iterator over the indices of the non-overlapping occurrences
+    /// of `pattern` in the vector, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, true, false, true, true];
+    /// let matches: Vec<usize> = vec.find_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 4]);
+    /// ```
+    #[inline]
+    pub fn find_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(&self.buf, self.len, pattern, false) }
+    }
+
+    /// Returns an iterator over the indices of all occurrences of `pattern` in
+    /// the vector, including overlapping ones, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, true, false, true, true];
+    /// let matches: Vec<usize> = vec.find_overlapping_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 1, 4]);
+    /// ```
+    #[inline]
+    pub fn find_overlapping_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(&self.buf, self.len, pattern, true) }
+    }
+
+    /// Returns `true` if `pattern` occurs anywhere in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, false, true, true];
+    /// assert!(vec.contains_pattern(&bitvec![false, true]));
+    /// assert!(!vec.contains_pattern(&bitvec![false, false]));
+    /// ```
+    #[inline]
+    pub fn contains_pattern<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        self.find(pattern).is_some()
+    }
+
+    /// Returns `true` if the vector begins with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, false, true, true];
+    /// assert!(vec.starts_with(&bitvec![true, false]));
+    /// assert!(!vec.starts_with(&bitvec![true, true]));
+    /// ```
+    #[inline]
+    pub fn starts_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { starts_with(&self.buf, self.len, pattern) }
+    }
+
+    /// Returns `true` if the vector ends with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, false, true, true];
+    /// assert!(vec.ends_with(&bitvec![true, true]));
+    /// assert!(!vec.ends_with(&bitvec![false, true]));
+    /// ```
+    #[inline]
+    pub fn ends_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { ends_with(&self.buf, self.len, pattern) }
+    }
+}
+
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+pub(crate) unsafe fn find<B: Allocator>(
+    buf: &[Word],
+    len: usize,
+    pattern: &BitVec<B>,
+) -> Option<usize> {
+    let pattern = Pattern::new(pattern);
+    let last = len.checked_sub(pattern.len)?;
+    let mut start = 0;
+    while start <= last {
+        let end = last.min(start + (Word::BITS - 1)) + 1;
+        let matches = unsafe { pattern.matches_in(buf, len, start..end) };
+        if matches != Word::CLEAR {
+            return Some(start + matches.leading_zeros());
+        }
+        start = end;
+    }
+    None
+}
+
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+pub(crate) unsafe fn rfind<B: Allocator>(
+    buf: &[Word],
+    len: usize,
+    pattern: &BitVec<B>,
+) -> Option<usize> {
+    let pattern = Pattern::new(pattern);
+    let mut end = len.checked_sub(pattern.len)? + 1;
+    while end > 0 {
+        let start = end.saturating_sub(Word::BITS);
+        let matches = unsafe { pattern.matches_in(buf, len, start..end) };
+        if matches != Word::CLEAR {
+            return Some(start + (Word::BITS - 1 - matches.trailing_zeros()));
+        }
+        end = start;
+    }
+    None
+}
+
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+pub(crate) unsafe fn starts_with<B: Allocator>(
+    buf: &[Word],
+    len: usize,
+    pattern: &BitVec<B>,
+) -> bool {
+    let pattern = Pattern::new(pattern);
+    pattern.len <= len && unsafe { pattern.matches_at(buf, 0) }
+}
+
+/// # Safety
+///
+/// The buffer must hold at least `len` bits.
+pub(crate) unsafe fn ends_with<B: Allocator>(
+    buf: &[Word],
+    len: usize,
+    pattern: &BitVec<B>,
+) -> bool {
+    let pattern = Pattern::new(pattern);
+    match len.checked_sub(pattern.len) {
+        None => false,
+        Some(index) => unsafe { pattern.matches_at(buf, index) },
+    }
+}
+
+/// A search pattern, matched against a whole word of candidate positions at
+/// once: each bit of the pattern is compared with the text shifted by its index,
+/// and the positions that survive every comparison are the occurrences.
+#[derive(Debug, Clone)]
+struct Pattern {
+    len: usize,
+    buf: Vec<Word>,
+}
+
+impl Pattern {
+    fn new<A: Allocator>(pattern: &BitVec<A>) -> Self {
+        let len = pattern.len;
+        let buf = unsafe { pattern.buf.get_unchecked(..pattern.buf_used()) }.to_vec();
+        Self { len, buf }
+    }
+
+    /// Returns the positions in `range` at which the pattern occurs within the
+    /// first `len` bits of `buf`, as a word whose most significant bit stands for
+    /// `range.start`.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must hold at least `len` bits, and the range must be nonempty,
+    /// span at most `Word::BITS` positions and end by `len - self.len + 1`.
+    unsafe fn matches_in(&self, buf: &[Word], len: usize, range: Range<usize>) -> Word {
+        let buf_used = len.div_ceil(Word::BITS);
+        let mut matches = !Word::CLEAR << (Word::BITS - range.len());
+        for index in 0..self.len {
+            if matches == Word::CLEAR {
+                break;
+            }
+            // The bits from `range.start + index` on, one per candidate. Those
+            // past `len` only affect candidates outside the range.
+            let loc = Loc::new(range.start + index);
+            let mut window = unsafe { *buf.get_unchecked(loc.period) } << loc.offset;
+            if loc.offset != 0 && loc.period + 1 < buf_used {
+                let next = unsafe { *buf.get_unchecked(loc.period + 1) };
+                window |= next >> (Word::BITS - loc.offset);
+            }
+            let loc = Loc::new(index);
+            if unsafe { self.buf.get_unchecked(loc.period) }.get(loc.offset) {
+                matches &= window;
+            } else {
+                matches &= !window;
+            }
+        }
+        matches
+    }
+
+    /// # Safety
+    ///
+    /// The buffer must hold at least `index + self.len` bits.
+    unsafe fn matches_at(&self, buf: &[Word], index: usize) -> bool {
+        (0..self.len).step_by(Word::BITS).all(|start| {
+            let end = self.len.min(start + Word::BITS);
+            let bits = unsafe { load_bits(buf, index + start..index + end) };
+            bits == unsafe { load_bits(&self.buf, start..end) }
+        })
+    }
+}
+
+/// An iterator over the indices at which a pattern occurs in a bit vector.
+///
+/// This struct is created by [`BitVec::find_iter`] and
+/// [`BitVec::find_overlapping_iter`], and by their counterparts on
+/// [`BitBox`](crate::BitBox) and [`SmallBitVec`](crate::SmallBitVec).
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    buf: &'a [Word],
+    len: usize,
+    pattern: Pattern,
+    step: usize,
+    next: usize,
+    block: Range<usize>,
+    matches: Word,
+}
+
+impl<'a> Matches<'a> {
+    /// # Safety
+    ///
+    /// The buffer must hold at least `len` bits.
+    pub(crate) unsafe fn new<B: Allocator>(
+        buf: &'a [Word],
+        len: usize,
+        pattern: &BitVec<B>,
+        overlapping: bool,
+    ) -> Self {
+        let pattern = Pattern::new(pattern);
+        let step = if overlapping { 1 } else { pattern.len.max(1) };
+        let next = 0;
+        let block = 0..0;
+        let matches = Word::CLEAR;
+        Self {
+            buf,
+            len,
+            pattern,
+            step,
+            next,
+            block,
+            matches,
+        }
+    }
+}
+
+impl Iterator for Matches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.len.checked_sub(self.pattern.len)?;
+        loop {
+            if self.next < self.block.end {
+                // Drop the occurrences skipped over by the last step.
+                self.matches &= !Word::CLEAR >> (self.next - self.block.start);
+            } else if self.next <= last {
+                let end = last.min(self.next + (Word::BITS - 1)) + 1;
+                self.block = self.next..end;
+                let block = self.block.clone();
+                self.matches = unsafe { self.pattern.matches_in(self.buf, self.len, block) };
+            } else {
+                return None;
+            }
+
+            if self.matches != Word::CLEAR {
+                let index = self.block.start + self.matches.leading_zeros();
+                self.next = index + self.step;
+                return Some(index);
+            }
+            self.next = self.block.end;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = match self.len.checked_sub(self.pattern.len) {
+            Some(last) => (last + 1).saturating_sub(self.next).div_ceil(self.step),
+            None => 0,
+        };
+        (0, Some(upper))
+    }
+}
+
+impl FusedIterator for Matches<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+    use crate::primitive::Bit;
+
+    fn naive(bits: &[Bit], pattern: &[Bit], step: usize) -> Vec<usize> {
+        let mut matches = Vec::new();
+        let mut index = 0;
+        while index + pattern.len() <= bits.len() {
+            if bits[index..index + pattern.len()] == *pattern {
+                matches.push(index);
+                index += step;
+            } else {
+                index += 1;
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn test_find() {
+        let len = Word::BITS * 3 + 5;
+        let bits: Vec<Bit> = (0..len)
+            .map(|index| index % 5 == 0 || index % 7 == 0)
+            .collect();
+        let mut vec = BitVec::from(&bits[..]);
+        vec.push_unused_word();
+
+        for (start, pattern_len) in [
+            (0, 0),
+            (0, 2),
+            (3, 5),
+            (10, Word::BITS + 3),
+            (1, len - 1),
+            (0, len),
+        ] {
+            let pattern = &bits[start..start + pattern_len];
+            let mut pattern_vec = BitVec::from(pattern);
+            pattern_vec.push(true).pop();
+            pattern_vec.push_unused_word();
+
+            let overlapping = naive(&bits, pattern, 1);
+            assert_eq!(vec.find(&pattern_vec), overlapping.first().copied());
+            assert_eq!(vec.rfind(&pattern_vec), overlapping.last().copied());
+            assert_eq!(
+                vec.find_overlapping_iter(&pattern_vec).collect::<Vec<_>>(),
+                overlapping
+            );
+            assert_eq!(
+                vec.find_iter(&pattern_vec).collect::<Vec<_>>(),
+                naive(&bits, pattern, pattern_len.max(1))
+            );
+            assert!(vec.contains_pattern(&pattern_vec));
+            assert_eq!(vec.starts_with(&pattern_vec), bits.starts_with(pattern));
+            assert_eq!(vec.ends_with(&pattern_vec), bits.ends_with(pattern));
+        }
+
+        let pattern = bitvec![true; 3];
+        assert_eq!(vec.find(&pattern), None);
+        assert_eq!(vec.rfind(&pattern), None);
+        assert_eq!(vec.find_iter(&pattern).next(), None);
+        assert!(!vec.contains_pattern(&pattern));
+        assert!(!bitvec![true].starts_with(&pattern));
+        assert!(!bitvec![true].ends_with(&pattern));
+    }
+
+    #[test]
+    fn test_find_random() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let bits: Vec<Bit> = (0..Word::BITS * 5 + 17)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state % 3 == 0
+            })
+            .collect();
+        let vec = BitVec::from(&bits[..]);
+
+        for pattern_len in [1, 2, 5, 13, Word::BITS - 1, Word::BITS, Word::BITS + 5] {
+            for start in [0, 7, Word::BITS - 3, Word::BITS * 2 + 1] {
+                let mut pattern = bits[start..start + pattern_len].to_vec();
+                for flipped in [None, Some(pattern_len / 2)] {
+                    if let Some(index) = flipped {
+                        pattern[index] = !pattern[index];
+                    }
+                    let pattern_vec = BitVec::from(&pattern[..]);
+                    let overlapping = naive(&bits, &pattern, 1);
+                    assert_eq!(vec.find(&pattern_vec), overlapping.first().copied());
+                    assert_eq!(vec.rfind(&pattern_vec), overlapping.last().copied());
+                    assert_eq!(
+                        vec.find_overlapping_iter(&pattern_vec).collect::<Vec<_>>(),
+                        overlapping
+                    );
+                    assert_eq!(
+                        vec.find_iter(&pattern_vec).collect::<Vec<_>>(),
+                        naive(&bits, &pattern, pattern_len)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_size_hint() {
+        let vec = bitvec![true; 10];
+        let mut matches = vec.find_iter(&bitvec![true; 3]);
+        assert_eq!(matches.size_hint(), (0, Some(3)));
+        assert_eq!(matches.next(), Some(0));
+        assert_eq!(matches.size_hint(), (0, Some(2)));
+        assert_eq!(matches.by_ref().count(), 2);
+        assert_eq!(matches.size_hint(), (0, Some(0)));
+
+        let matches = vec.find_overlapping_iter(&bitvec![true; 3]);
+        assert_eq!(matches.size_hint(), (0, Some(8)));
+        assert_eq!(matches.count(), 8);
+
+        let matches = vec.find_iter(&bitvec![true; 11]);
+        assert_eq!(matches.size_hint(), (0, Some(0)));
+    }
+}
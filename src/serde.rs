@@ -1,75 +1,282 @@
-use crate::BitVec;
+//! Serde support for [`BitVec`], [`BitBox`] and [`SmallBitVec`].
+//!
+//! By default, deserialization tolerates a `len` that exceeds the bits in the
+//! buffer, and surplus bytes after them. The [`strict`] and [`canonical`]
+//! modules reject such input instead, for use with `#[serde(with = "...")]`.
+//!
+//! The `as_*` modules encode a vector in an alternative shape instead, such as
+//! a sequence of booleans or a list of set-bit positions, and [`sparse`] picks
+//! between a dense and a sparse shape by density.
+
+use crate::convert::words_to_bytes;
 use crate::primitive::{Byte, Word};
+use crate::{BitBox, BitVec, Iter, SmallBitVec};
+use alloc::string::String;
 use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
 use core::fmt;
-use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
-use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+mod adapters;
+mod borrowed;
+pub mod sparse;
+
+pub use adapters::{as_base64, as_bools, as_hex, as_indices, as_string};
+pub use borrowed::{BorrowedBitVec, BorrowedIter};
 
 const FIELDS: &[&str] = &["len", "buf"];
 
-impl Serialize for BitVec {
+impl<A: Allocator> Serialize for BitVec<A> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_words(&self.buf, self.len, serializer)
+    }
+}
+
+impl<A: Allocator> Serialize for BitBox<A> {
+    #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut vec = serializer.serialize_struct("BitVec", 2)?;
-        vec.serialize_field("len", &self.len)?;
-        vec.serialize_field("buf", &BufProxy(self))?;
-        vec.end()
+        serialize_words(self.as_bitslice().buf(), self.len(), serializer)
     }
 }
 
+impl<const N: usize> Serialize for SmallBitVec<N> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_words(self.as_bitslice().buf(), self.len(), serializer)
+    }
+}
+
+/// Serializes the first `len` bits of `buf` in the representation of a
+/// [`BitVec`].
+fn serialize_words<S>(buf: &[Word], len: usize, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        return serializer.serialize_str(&to_bit_string(Iter::new(buf, 0..len)));
+    }
+
+    let mut vec = serializer.serialize_struct("BitVec", 2)?;
+    vec.serialize_field("len", &len)?;
+    vec.serialize_field("buf", &BufProxy { buf, len })?;
+    vec.end()
+}
+
+/// Returns `bits` as a string of `0` and `1` prefixed with `0b`.
+fn to_bit_string(bits: Iter<'_>) -> String {
+    let mut string = String::with_capacity(bits.len() + 2);
+    string.push_str("0b");
+    string.extend(bits.map(|bit| if bit { '1' } else { '0' }));
+    string
+}
+
+/// Parses a string produced by [`to_bit_string`].
+fn parse_bit_string(value: &str) -> Option<BitVec> {
+    value
+        .strip_prefix("0b")?
+        .bytes()
+        .map(|byte| match byte {
+            b'0' => Some(false),
+            b'1' => Some(true),
+            _ => None,
+        })
+        .collect()
+}
+
 #[derive(Debug)]
-struct BufProxy<'a>(&'a BitVec);
+struct BufProxy<'a> {
+    buf: &'a [Word],
+    len: usize,
+}
 
 impl Serialize for BufProxy<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let seq_len = self.0.len.div_ceil(Byte::BITS as usize);
-        let head_words = seq_len / Word::BYTES;
-        let tail_bytes = seq_len % Word::BYTES;
-        let mut seq = serializer.serialize_seq(Some(seq_len))?;
-
-        let head = unsafe { self.0.buf.get_unchecked(..head_words) };
-        for word in head {
-            let word = word.to_byte_array();
-            for byte in word {
-                seq.serialize_element(&byte)?;
-            }
-        }
+        let bytes = unsafe { words_to_bytes(self.buf, self.len) };
+        serializer.serialize_bytes(&bytes)
+    }
+}
 
-        if tail_bytes != 0 {
-            let word = unsafe { self.0.buf.get_unchecked(head_words) };
-            let word = word.to_byte_array();
-            let tail = unsafe { word.get_unchecked(0..tail_bytes) };
-            for byte in tail {
-                seq.serialize_element(&byte)?;
-            }
-        }
+impl<'de> Deserialize<'de> for BitVec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_with(deserializer, Validation::Lenient)
+    }
+}
 
-        seq.end()
+impl<'de> Deserialize<'de> for BitBox {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BitVec::deserialize(deserializer).map(BitVec::into_boxed_bitslice)
     }
 }
 
-impl<'de> Deserialize<'de> for BitVec {
+impl<'de, const N: usize> Deserialize<'de> for SmallBitVec<N> {
+    #[inline]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("BitVec", FIELDS, BitVecVisitor)
+        BitVec::deserialize(deserializer).map(Self::from)
     }
 }
 
-#[derive(Debug)]
-struct BitVecVisitor;
+/// Strict deserialization, rejecting a `len` that exceeds the bits in the
+/// buffer and any bytes beyond the last one holding a bit.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::strict")]`.
+pub mod strict {
+    use super::Validation;
+    use crate::BitVec;
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Serializes the vector as usual.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        vec.serialize(serializer)
+    }
+
+    /// Deserializes a vector, rejecting inconsistent lengths.
+    #[inline]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_with(deserializer, Validation::Strict)
+    }
+}
+
+/// Like [`strict`], but additionally rejects nonzero padding bits after the
+/// last bit, so that every vector has exactly one accepted encoding.
+pub mod canonical {
+    use super::Validation;
+    use crate::BitVec;
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Serializes the vector as usual.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        vec.serialize(serializer)
+    }
+
+    /// Deserializes a vector, rejecting inconsistent lengths and nonzero
+    /// padding bits.
+    #[inline]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_with(deserializer, Validation::Canonical)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Validation {
+    Lenient,
+    Strict,
+    Canonical,
+}
+
+fn deserialize_with<'de, D>(deserializer: D, validation: Validation) -> Result<BitVec, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let visitor = BitVecVisitor { validation };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_struct("BitVec", FIELDS, visitor)
+    }
+}
+
+/// Builds a vector of `len` bits from `buf`, checking the two against each
+/// other as `validation` requires.
+fn build<E: Error>(len: usize, buf: Vec<Byte>, validation: Validation) -> Result<BitVec, E> {
+    validate(len, &buf, validation)?;
+    let mut vec = BitVec::from(buf);
+    vec.len = vec.len.min(len);
+    Ok(vec)
+}
+
+/// Checks `len` against the bytes of `buf` as `validation` requires.
+fn validate<E: Error>(len: usize, buf: &[Byte], validation: Validation) -> Result<(), E> {
+    if validation != Validation::Lenient {
+        let expected = len.div_ceil(Byte::BITS as usize);
+        if buf.len() < expected {
+            return Err(Error::custom(format_args!(
+                "length {len} exceeds the {} bytes in the buffer",
+                buf.len()
+            )));
+        }
+        if buf.len() > expected {
+            return Err(Error::custom(format_args!(
+                "buffer has {} bytes, but a length of {len} needs only {expected}",
+                buf.len()
+            )));
+        }
+    }
+
+    if validation == Validation::Canonical {
+        let used = len % Byte::BITS as usize;
+        if used != 0
+            && buf
+                .last()
+                .is_some_and(|last| last & (Byte::MAX >> used) != 0)
+        {
+            return Err(Error::custom(format_args!(
+                "padding bits after bit {len} are not zero"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BitVecVisitor {
+    validation: Validation,
+}
 
 impl<'de> Visitor<'de> for BitVecVisitor {
     type Value = BitVec;
 
     fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str("struct BitVec")
+        formatter.write_str("a `0b` bit string or struct BitVec")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        parse_bit_string(value).ok_or_else(|| Error::invalid_value(Unexpected::Str(value), &self))
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -79,14 +286,11 @@ impl<'de> Visitor<'de> for BitVecVisitor {
         let len: usize = seq
             .next_element()?
             .ok_or_else(|| Error::invalid_length(0, &self))?;
-        let buf: Vec<Byte> = seq
+        let Buf(buf) = seq
             .next_element()?
             .ok_or_else(|| Error::invalid_length(1, &self))?;
 
-        let mut vec = BitVec::from(buf);
-        vec.len = vec.len.min(len);
-
-        Ok(vec)
+        build(len, buf, self.validation)
     }
 
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -94,7 +298,7 @@ impl<'de> Visitor<'de> for BitVecVisitor {
         A: MapAccess<'de>,
     {
         let mut len: Option<usize> = None;
-        let mut buf: Option<Vec<Byte>> = None;
+        let mut buf: Option<Buf> = None;
 
         while let Some(key) = map.next_key()? {
             match key {
@@ -114,12 +318,66 @@ impl<'de> Visitor<'de> for BitVecVisitor {
         }
 
         let len = len.ok_or_else(|| Error::missing_field("len"))?;
-        let buf = buf.ok_or_else(|| Error::missing_field("buf"))?;
+        let Buf(buf) = buf.ok_or_else(|| Error::missing_field("buf"))?;
 
-        let mut vec = BitVec::from(buf);
-        vec.len = vec.len.min(len);
+        build(len, buf, self.validation)
+    }
+}
+
+/// The serialized buffer, accepted either as a byte string or, for data
+/// written by older versions, as a sequence of bytes.
+#[derive(Debug)]
+struct Buf(Vec<Byte>);
+
+impl<'de> Deserialize<'de> for Buf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(BufVisitor)
+    }
+}
+
+#[derive(Debug)]
+struct BufVisitor;
+
+impl<'de> Visitor<'de> for BufVisitor {
+    type Value = Buf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte string or a sequence of bytes")
+    }
+
+    fn visit_bytes<E>(self, value: &[Byte]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(Buf(value.to_vec()))
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [Byte]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_bytes(value)
+    }
 
-        Ok(vec)
+    fn visit_byte_buf<E>(self, value: Vec<Byte>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(Buf(value))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut buf = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            buf.push(byte);
+        }
+        Ok(Buf(buf))
     }
 }
 
@@ -162,8 +420,14 @@ impl Visitor<'_> for FieldVisitor {
 
 #[cfg(test)]
 mod tests {
-    use crate::bitvec;
-    use serde_test::{Token, assert_de_tokens, assert_ser_tokens};
+    use crate::primitive::Word;
+    use crate::{BitVec, SmallBitVec, bitvec};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_test::{Compact, Readable};
+    use serde_test::{
+        Configure, Token, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens,
+        assert_tokens,
+    };
 
     #[test]
     fn test_serialize() {
@@ -177,12 +441,10 @@ mod tests {
                 Token::Str("len"),
                 Token::U64(4),
                 Token::Str("buf"),
-                Token::Seq { len: Some(1) },
-                Token::U8(0b11000000),
-                Token::SeqEnd,
+                Token::Bytes(&[0b11000000]),
                 Token::StructEnd,
             ];
-            assert_ser_tokens(&vec, &tokens);
+            assert_ser_tokens(&vec.compact(), &tokens);
         }
 
         {
@@ -197,13 +459,10 @@ mod tests {
                 Token::Str("len"),
                 Token::U64(10),
                 Token::Str("buf"),
-                Token::Seq { len: Some(2) },
-                Token::U8(0b11110000),
-                Token::U8(0b11000000),
-                Token::SeqEnd,
+                Token::Bytes(&[0b11110000, 0b11000000]),
                 Token::StructEnd,
             ];
-            assert_ser_tokens(&vec, &tokens);
+            assert_ser_tokens(&vec.compact(), &tokens);
         }
     }
 
@@ -219,7 +478,7 @@ mod tests {
                 Token::SeqEnd,
             ];
             let expected = bitvec![true, true, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -235,7 +494,7 @@ mod tests {
             let expected = bitvec![
                 true, true, true, true, false, false, false, false, true, true
             ];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -248,7 +507,7 @@ mod tests {
                 Token::SeqEnd,
             ];
             let expected = bitvec![true, true, true, true, false, false, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
     }
 
@@ -266,7 +525,7 @@ mod tests {
                 Token::MapEnd,
             ];
             let expected = bitvec![true, true, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -284,7 +543,7 @@ mod tests {
             let expected = bitvec![
                 true, true, true, true, false, false, false, false, true, true
             ];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -299,7 +558,7 @@ mod tests {
                 Token::MapEnd,
             ];
             let expected = bitvec![true, true, true, true, false, false, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
     }
 
@@ -320,7 +579,7 @@ mod tests {
                 Token::StructEnd,
             ];
             let expected = bitvec![true, true, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -341,7 +600,7 @@ mod tests {
             let expected = bitvec![
                 true, true, true, true, false, false, false, false, true, true
             ];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
 
         {
@@ -359,7 +618,200 @@ mod tests {
                 Token::StructEnd,
             ];
             let expected = bitvec![true, true, true, true, false, false, false, false];
-            assert_de_tokens(&expected, &tokens);
+            assert_de_tokens(&expected.compact(), &tokens);
         }
     }
+
+    #[test]
+    fn test_deserialize_bytes() {
+        let expected = bitvec![
+            true, true, true, true, false, false, false, false, true, true
+        ];
+        for buf in [
+            Token::Bytes(&[0b11110000, 0b11110000]),
+            Token::BorrowedBytes(&[0b11110000, 0b11110000]),
+            Token::ByteBuf(&[0b11110000, 0b11110000]),
+        ] {
+            let tokens = [
+                Token::Struct {
+                    name: "BitVec",
+                    len: 2,
+                },
+                Token::Str("len"),
+                Token::U64(10),
+                Token::Str("buf"),
+                buf,
+                Token::StructEnd,
+            ];
+            assert_de_tokens(&expected.clone().compact(), &tokens);
+        }
+
+        let tokens = [
+            Token::Seq { len: None },
+            Token::U64(4),
+            Token::Bytes(&[0b11001100]),
+            Token::SeqEnd,
+        ];
+        assert_de_tokens(&bitvec![true, true, false, false].compact(), &tokens);
+    }
+
+    #[test]
+    fn test_human_readable() {
+        let vec = bitvec![true, true, false, false, false, false, false, false, true];
+        assert_ser_tokens(&vec.clone().readable(), &[Token::Str("0b110000001")]);
+        assert_de_tokens(&vec.readable(), &[Token::Str("0b110000001")]);
+        assert_de_tokens(&BitVec::new().readable(), &[Token::Str("0b")]);
+
+        let tokens = [
+            Token::Map { len: None },
+            Token::Str("len"),
+            Token::U64(4),
+            Token::Str("buf"),
+            Token::Bytes(&[0b11001100]),
+            Token::MapEnd,
+        ];
+        assert_de_tokens(&bitvec![true, true, false, false].readable(), &tokens);
+
+        let message =
+            "invalid value: string \"0b102\", expected a `0b` bit string or struct BitVec";
+        assert_de_tokens_error::<Readable<BitVec>>(&[Token::Str("0b102")], message);
+        let message = "invalid value: string \"101\", expected a `0b` bit string or struct BitVec";
+        assert_de_tokens_error::<Readable<BitVec>>(&[Token::Str("101")], message);
+    }
+
+    #[test]
+    fn test_small() {
+        let vec =
+            SmallBitVec::<1>::from([true, true, false, false, false, false, false, false, true]);
+        assert_ser_tokens(&vec.clone().readable(), &[Token::Str("0b110000001")]);
+        assert_de_tokens(&vec.clone().readable(), &[Token::Str("0b110000001")]);
+        let tokens = compact_tokens(9, &[0b11000000, 0b10000000]);
+        assert_ser_tokens(&vec.clone().compact(), &tokens);
+        assert_de_tokens(&vec.compact(), &tokens);
+
+        let mut vec = SmallBitVec::<0>::from([0xff; 3]);
+        vec.pop();
+        assert!(vec.spilled());
+        let tokens = compact_tokens(23, &[0xff, 0xff, 0xfe]);
+        assert_ser_tokens(&vec.clone().compact(), &tokens);
+        assert_de_tokens(&vec.compact(), &tokens);
+    }
+
+    #[test]
+    fn test_boxed() {
+        let vec = bitvec![true, true, false, false, false, false, false, false, true];
+        let boxed = vec.into_boxed_bitslice();
+        assert_ser_tokens(&boxed.clone().readable(), &[Token::Str("0b110000001")]);
+        assert_de_tokens(&boxed.clone().readable(), &[Token::Str("0b110000001")]);
+        let tokens = compact_tokens(9, &[0b11000000, 0b10000000]);
+        assert_ser_tokens(&boxed.clone().compact(), &tokens);
+        assert_de_tokens(&boxed.compact(), &tokens);
+
+        let mut vec = bitvec![0xff, 0xff, 0xff];
+        vec.pop();
+        let tokens = compact_tokens(23, &[0xff, 0xff, 0xfe]);
+        assert_ser_tokens(&vec.into_boxed_bitslice().compact(), &tokens);
+    }
+
+    fn compact_tokens(len: u64, buf: &'static [u8]) -> [Token; 6] {
+        [
+            Token::Struct {
+                name: "BitVec",
+                len: 2,
+            },
+            Token::Str("len"),
+            Token::U64(len),
+            Token::Str("buf"),
+            Token::Bytes(buf),
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    fn test_strict() {
+        #[derive(Debug, PartialEq)]
+        struct Strict(BitVec);
+
+        impl<'de> Deserialize<'de> for Strict {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                super::strict::deserialize(deserializer).map(Strict)
+            }
+        }
+
+        let tokens = compact_tokens(10, &[0b11110000, 0b11111111]);
+        let expected = bitvec![
+            true, true, true, true, false, false, false, false, true, true
+        ];
+        assert_de_tokens(&Strict(expected).compact(), &tokens);
+        assert_de_tokens(&Strict(BitVec::new()).compact(), &compact_tokens(0, &[]));
+
+        let tokens = compact_tokens(17, &[0b11110000, 0b11111111]);
+        let message = "length 17 exceeds the 2 bytes in the buffer";
+        assert_de_tokens_error::<Compact<Strict>>(&tokens, message);
+
+        let tokens = compact_tokens(8, &[0b11110000, 0b11111111]);
+        let message = "buffer has 2 bytes, but a length of 8 needs only 1";
+        assert_de_tokens_error::<Compact<Strict>>(&tokens, message);
+    }
+
+    #[test]
+    fn test_canonical() {
+        #[derive(Debug, PartialEq)]
+        struct Canonical(BitVec);
+
+        impl<'de> Deserialize<'de> for Canonical {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                super::canonical::deserialize(deserializer).map(Canonical)
+            }
+        }
+
+        let tokens = compact_tokens(10, &[0b11110000, 0b11000000]);
+        let expected = bitvec![
+            true, true, true, true, false, false, false, false, true, true
+        ];
+        assert_de_tokens(&Canonical(expected).compact(), &tokens);
+        let tokens = compact_tokens(8, &[0b11110001]);
+        assert_de_tokens(
+            &Canonical(BitVec::from(&[0b11110001][..])).compact(),
+            &tokens,
+        );
+
+        let tokens = compact_tokens(10, &[0b11110000, 0b11100000]);
+        let message = "padding bits after bit 10 are not zero";
+        assert_de_tokens_error::<Compact<Canonical>>(&tokens, message);
+
+        let tokens = compact_tokens(8, &[0b11110000, 0]);
+        let message = "buffer has 2 bytes, but a length of 8 needs only 1";
+        assert_de_tokens_error::<Compact<Canonical>>(&tokens, message);
+    }
+
+    #[test]
+    fn test_canonical_round_trip() {
+        #[derive(Debug, PartialEq)]
+        struct Canonical(BitVec);
+
+        impl Serialize for Canonical {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::canonical::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Canonical {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                super::canonical::deserialize(deserializer).map(Canonical)
+            }
+        }
+
+        // Popping leaves the bit set in the padding, which must not be written.
+        let mut vec = bitvec![0xff; 2];
+        vec.pop();
+        vec.push_unused_word();
+        let tokens = compact_tokens(15, &[0xff, 0xfe]);
+        assert_tokens(&Canonical(vec).compact(), &tokens);
+
+        let mut vec = bitvec![true; Word::BITS + 1];
+        vec.pop();
+        let tokens = compact_tokens(Word::BITS as u64, &[0xff; Word::BYTES]);
+        assert_tokens(&Canonical(vec).compact(), &tokens);
+    }
 }
@@ -0,0 +1,426 @@
+use super::{Validation, build, parse_bit_string, to_bit_string};
+use crate::primitive::Byte;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::{Error, Unexpected};
+
+/// Encodes a vector as a sequence of booleans, such as `[true, false]`.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::as_bools")]`.
+pub mod as_bools {
+    use crate::BitVec;
+    use crate::primitive::Bit;
+    use alloc::vec::Vec;
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the vector as a sequence of booleans.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        serializer.collect_seq(vec.iter())
+    }
+
+    /// Deserializes a vector from a sequence of booleans.
+    #[inline]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bits = Vec::<Bit>::deserialize(deserializer)?;
+        Ok(bits.into_iter().collect())
+    }
+}
+
+/// Encodes a vector as a binary string, such as `"0b10"`, in every format.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::as_string")]`.
+pub mod as_string {
+    use crate::BitVec;
+    use alloc::string::String;
+    use allocator_api2::alloc::Allocator;
+    use serde::de::{Error, Unexpected};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the vector as a binary string.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        serializer.serialize_str(&super::to_bit_string(vec.iter()))
+    }
+
+    /// Deserializes a vector from a binary string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        super::parse_bit_string(&string).ok_or_else(|| {
+            Error::invalid_value(
+                Unexpected::Str(&string),
+                &"a binary string prefixed with `0b`",
+            )
+        })
+    }
+}
+
+/// Encodes a vector as a `(len, hex)` pair, where `hex` holds the bytes of the
+/// vector as lowercase hexadecimal digits, padded with zero bits.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::as_hex")]`.
+pub mod as_hex {
+    use crate::BitVec;
+    use alloc::string::String;
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes the vector as a `(len, hex)` pair.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        (vec.len(), super::encode_hex(&vec.to_bytes())).serialize(serializer)
+    }
+
+    /// Deserializes a vector from a `(len, hex)` pair, rejecting a `len` that
+    /// does not match the number of digits.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (len, hex) = <(usize, String)>::deserialize(deserializer)?;
+        let bytes = super::decode_hex(&hex)?;
+        super::build(len, bytes, super::Validation::Strict)
+    }
+}
+
+/// Encodes a vector as a `(len, base64)` pair, where `base64` holds the bytes
+/// of the vector in the standard, padded Base64 alphabet.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::as_base64")]`.
+pub mod as_base64 {
+    use crate::BitVec;
+    use alloc::string::String;
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes the vector as a `(len, base64)` pair.
+    #[inline]
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        (vec.len(), super::encode_base64(&vec.to_bytes())).serialize(serializer)
+    }
+
+    /// Deserializes a vector from a `(len, base64)` pair, rejecting a `len`
+    /// that does not match the decoded bytes.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (len, base64) = <(usize, String)>::deserialize(deserializer)?;
+        let bytes = super::decode_base64(&base64)?;
+        super::build(len, bytes, super::Validation::Strict)
+    }
+}
+
+/// Encodes a vector as a `(len, indices)` pair, where `indices` lists the
+/// positions of the set bits in ascending order. Suited to sparse vectors.
+///
+/// Apply it to a field with `#[serde(with = "bitvek::serde::as_indices")]`.
+pub mod as_indices {
+    use crate::BitVec;
+    use alloc::vec::Vec;
+    use allocator_api2::alloc::Allocator;
+    use serde::de::Error;
+    use serde::ser::SerializeTuple;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the vector as a `(len, indices)` pair.
+    pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Allocator,
+        S: Serializer,
+    {
+        let indices: Vec<usize> = vec
+            .iter_runs()
+            .filter(|&(bit, _, _)| bit)
+            .flat_map(|(_, start, len)| start..start + len)
+            .collect();
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&vec.len())?;
+        tuple.serialize_element(&indices)?;
+        tuple.end()
+    }
+
+    /// Deserializes a vector from a `(len, indices)` pair, rejecting indices
+    /// that are out of bounds and lengths that cannot be allocated.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (len, indices) = <(usize, Vec<usize>)>::deserialize(deserializer)?;
+        let mut vec = BitVec::try_zeroed(len).map_err(Error::custom)?;
+        for index in indices {
+            if index >= len {
+                return Err(Error::custom(format_args!(
+                    "index {index} out of range for a length of {len}"
+                )));
+            }
+            unsafe { vec.set_unchecked(index, true) };
+        }
+        Ok(vec)
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_hex(bytes: &[Byte]) -> String {
+    let mut string = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        string.push(HEX_DIGITS[usize::from(byte >> 4)] as char);
+        string.push(HEX_DIGITS[usize::from(byte & 0xf)] as char);
+    }
+    string
+}
+
+fn decode_hex<E: Error>(value: &str) -> Result<Vec<Byte>, E> {
+    let invalid = || Error::invalid_value(Unexpected::Str(value), &"a string of hex digit pairs");
+    if value.len() % 2 != 0 {
+        return Err(invalid());
+    }
+    value
+        .as_bytes()
+        .chunks_exact(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16).ok_or_else(invalid)?;
+            let low = (pair[1] as char).to_digit(16).ok_or_else(invalid)?;
+            Ok((high << 4 | low) as Byte)
+        })
+        .collect()
+}
+
+fn encode_base64(bytes: &[Byte]) -> String {
+    let mut string = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = group >> (18 - 6 * index) & 0x3f;
+                string.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                string.push('=');
+            }
+        }
+    }
+    string
+}
+
+fn decode_base64<E: Error>(value: &str) -> Result<Vec<Byte>, E> {
+    let invalid = || Error::invalid_value(Unexpected::Str(value), &"a padded Base64 string");
+    let input = value.as_bytes();
+    if input.len() % 4 != 0 {
+        return Err(invalid());
+    }
+
+    let mut bytes = Vec::with_capacity(input.len() / 4 * 3);
+    let last = input.len() / 4;
+    for (index, chunk) in input.chunks_exact(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&char| char == b'=').count();
+        if padding > 2 || (padding != 0 && index + 1 != last) {
+            return Err(invalid());
+        }
+
+        let mut group = 0;
+        for &char in &chunk[..4 - padding] {
+            let sextet = BASE64_ALPHABET
+                .iter()
+                .position(|&digit| digit == char)
+                .ok_or_else(invalid)?;
+            group = group << 6 | sextet as u32;
+        }
+        group <<= 6 * padding;
+
+        let [_, group @ ..] = group.to_be_bytes();
+        bytes.extend_from_slice(&group[..3 - padding]);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BitVec, TryReserveError, bitvec};
+    use alloc::string::ToString;
+    use alloc::{format, vec};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+    macro_rules! adapter {
+        ($name:ident, $module:ident) => {
+            #[derive(Debug, PartialEq)]
+            struct $name(BitVec);
+
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    super::$module::serialize(&self.0, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    super::$module::deserialize(deserializer).map($name)
+                }
+            }
+        };
+    }
+
+    adapter!(Bools, as_bools);
+    adapter!(BitString, as_string);
+    adapter!(Hex, as_hex);
+    adapter!(Base64, as_base64);
+    adapter!(Indices, as_indices);
+
+    fn sample() -> BitVec {
+        let mut vec = bitvec![
+            true, false, true, true, false, false, false, false, false, true
+        ];
+        vec.push(true).pop();
+        vec
+    }
+
+    #[test]
+    fn test_as_bools() {
+        let mut tokens = vec![Token::Seq { len: Some(10) }];
+        tokens.extend(sample().iter().map(Token::Bool));
+        tokens.push(Token::SeqEnd);
+        assert_tokens(&Bools(sample()), &tokens);
+    }
+
+    #[test]
+    fn test_as_string() {
+        assert_tokens(&BitString(sample()), &[Token::Str("0b1011000001")]);
+        assert_tokens(&BitString(BitVec::new()), &[Token::Str("0b")]);
+        assert_de_tokens_error::<BitString>(
+            &[Token::Str("1011")],
+            "invalid value: string \"1011\", expected a binary string prefixed with `0b`",
+        );
+    }
+
+    #[test]
+    fn test_as_hex() {
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Str("b040"),
+            Token::TupleEnd,
+        ];
+        assert_tokens(&Hex(sample()), &tokens);
+
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Str("b04"),
+            Token::TupleEnd,
+        ];
+        let message = "invalid value: string \"b04\", expected a string of hex digit pairs";
+        assert_de_tokens_error::<Hex>(&tokens, message);
+
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(17),
+            Token::Str("b040"),
+            Token::TupleEnd,
+        ];
+        let message = "length 17 exceeds the 2 bytes in the buffer";
+        assert_de_tokens_error::<Hex>(&tokens, message);
+    }
+
+    #[test]
+    fn test_as_base64() {
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Str("sEA="),
+            Token::TupleEnd,
+        ];
+        assert_tokens(&Base64(sample()), &tokens);
+
+        for len in [0, 1, 8, 16, 17, 24, 25, 100] {
+            let vec: BitVec = (0..len).map(|index| index % 3 != 1).collect();
+            let bytes = vec.to_bytes();
+            let encoded = encode_base64(&bytes);
+            assert_eq!(encoded.len(), bytes.len().div_ceil(3) * 4);
+            assert_eq!(
+                decode_base64::<serde::de::value::Error>(&encoded),
+                Ok(bytes)
+            );
+        }
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+
+        for invalid in ["sEA", "s=A=", "sE==sEA=", "sE*="] {
+            let tokens = [
+                Token::Tuple { len: 2 },
+                Token::U64(10),
+                Token::Str(invalid),
+                Token::TupleEnd,
+            ];
+            let message =
+                format!("invalid value: string {invalid:?}, expected a padded Base64 string");
+            assert_de_tokens_error::<Base64>(&tokens, &message);
+        }
+    }
+
+    #[test]
+    fn test_as_indices() {
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Seq { len: Some(4) },
+            Token::U64(0),
+            Token::U64(2),
+            Token::U64(3),
+            Token::U64(9),
+            Token::SeqEnd,
+            Token::TupleEnd,
+        ];
+        assert_tokens(&Indices(sample()), &tokens);
+
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Seq { len: Some(1) },
+            Token::U64(10),
+            Token::SeqEnd,
+            Token::TupleEnd,
+        ];
+        let message = "index 10 out of range for a length of 10";
+        assert_de_tokens_error::<Indices>(&tokens, message);
+
+        let tokens = [
+            Token::Tuple { len: 2 },
+            Token::U64(1 << 60),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::TupleEnd,
+        ];
+        let message = TryReserveError::AllocError.to_string();
+        assert_de_tokens_error::<Indices>(&tokens, &message);
+    }
+}
@@ -0,0 +1,473 @@
+use super::{FIELDS, Field, Validation, validate};
+use crate::BitVec;
+use crate::primitive::{Bit, Byte};
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// A read-only bit vector borrowing its bytes from the deserializer input.
+///
+/// It deserializes from the compact form of [`BitVec`] without copying the
+/// buffer, which requires a format able to lend out byte strings, such as
+/// bincode or postcard reading from a slice. Like [`strict`], it rejects a
+/// `len` that does not match the number of bytes.
+///
+/// [`strict`]: super::strict
+#[derive(Debug, Clone, Copy)]
+pub struct BorrowedBitVec<'a> {
+    // Invariant: `self.bytes.len() == self.len.div_ceil(Byte::BITS)`
+    bytes: &'a [Byte],
+    len: usize,
+}
+
+impl<'a> BorrowedBitVec<'a> {
+    /// Creates a view of the first `len` bits of `bytes`, most significant bit
+    /// first, or returns `None` if `bytes` does not hold exactly the bytes
+    /// needed for `len` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// assert!(BorrowedBitVec::new(&[0b11000000], 2).is_some());
+    /// assert!(BorrowedBitVec::new(&[0b11000000], 9).is_none());
+    /// assert!(BorrowedBitVec::new(&[0b11000000, 0], 2).is_none());
+    /// ```
+    pub fn new(bytes: &'a [Byte], len: usize) -> Option<Self> {
+        if bytes.len() == len.div_ceil(Byte::BITS as usize) {
+            Some(Self { bytes, len })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b11000000], 2).unwrap();
+    /// assert_eq!(vec.len(), 2);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// assert!(BorrowedBitVec::new(&[], 0).unwrap().is_empty());
+    /// assert!(!BorrowedBitVec::new(&[0b11000000], 2).unwrap().is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the borrowed bytes, including any padding bits after the last
+    /// bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b11000000], 2).unwrap();
+    /// assert_eq!(vec.as_bytes(), [0b11000000]);
+    /// ```
+    #[inline]
+    pub const fn as_bytes(&self) -> &'a [Byte] {
+        self.bytes
+    }
+
+    /// Returns the bit at the specified index, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b10000000], 2).unwrap();
+    /// assert_eq!(vec.get(0), Some(true));
+    /// assert_eq!(vec.get(1), Some(false));
+    /// assert_eq!(vec.get(2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            None
+        } else {
+            Some(get_bit(self.bytes, index))
+        }
+    }
+
+    /// Returns an iterator over the bits of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b10100000], 3).unwrap();
+    /// assert!(vec.iter().eq([true, false, true]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> BorrowedIter<'a> {
+        let bytes = self.bytes;
+        let range = 0..self.len;
+        BorrowedIter { bytes, range }
+    }
+
+    /// Returns the number of set bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b11111111, 0b11111111], 10).unwrap();
+    /// assert_eq!(vec.count_ones(), 10);
+    /// ```
+    pub fn count_ones(&self) -> usize {
+        let full = self.len / Byte::BITS as usize;
+        let used = self.len % Byte::BITS as usize;
+        let head: usize = self.bytes[..full]
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum();
+        let tail = match used {
+            0 => 0,
+            _ => (self.bytes[full] & !(Byte::MAX >> used)).count_ones() as usize,
+        };
+        head + tail
+    }
+
+    /// Copies the bits of the vector into a new [`BitVec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    /// use bitvek::serde::BorrowedBitVec;
+    ///
+    /// let vec = BorrowedBitVec::new(&[0b10100000], 3).unwrap();
+    /// assert_eq!(vec.to_bitvec(), bitvec![true, false, true]);
+    /// ```
+    pub fn to_bitvec(&self) -> BitVec {
+        let mut vec = BitVec::from(self.bytes);
+        vec.len = self.len;
+        vec
+    }
+}
+
+impl<'a> IntoIterator for &BorrowedBitVec<'a> {
+    type Item = Bit;
+    type IntoIter = BorrowedIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for BorrowedBitVec<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BorrowedBitVec<'_> {}
+
+impl PartialEq<BitVec> for BorrowedBitVec<'_> {
+    #[inline]
+    fn eq(&self, other: &BitVec) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl PartialEq<BorrowedBitVec<'_>> for BitVec {
+    #[inline]
+    fn eq(&self, other: &BorrowedBitVec<'_>) -> bool {
+        other == self
+    }
+}
+
+impl Serialize for BorrowedBitVec<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            return self.to_bitvec().serialize(serializer);
+        }
+
+        let mut vec = serializer.serialize_struct("BitVec", 2)?;
+        vec.serialize_field("len", &self.len)?;
+        vec.serialize_field("buf", &BytesProxy(self))?;
+        vec.end()
+    }
+}
+
+#[derive(Debug)]
+struct BytesProxy<'a, 'b>(&'a BorrowedBitVec<'b>);
+
+impl Serialize for BytesProxy<'_, '_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Clear the padding of the last byte as `BitVec` does, copying the
+        // bytes only if it holds any set bits.
+        let BorrowedBitVec { bytes, len } = *self.0;
+        let used = len % Byte::BITS as usize;
+        match bytes.split_last() {
+            Some((&last, head)) if used != 0 && last & (Byte::MAX >> used) != 0 => {
+                let mut masked = Vec::with_capacity(bytes.len());
+                masked.extend_from_slice(head);
+                masked.push(last & !(Byte::MAX >> used));
+                serializer.serialize_bytes(&masked)
+            }
+            _ => serializer.serialize_bytes(bytes),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedBitVec<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("BitVec", FIELDS, BorrowedBitVecVisitor)
+    }
+}
+
+/// Checks the borrowed buffer against `len` before wrapping it.
+fn build<'a, E: Error>(len: usize, bytes: &'a [Byte]) -> Result<BorrowedBitVec<'a>, E> {
+    validate(len, bytes, Validation::Strict)?;
+    Ok(BorrowedBitVec { bytes, len })
+}
+
+#[derive(Debug)]
+struct BorrowedBitVecVisitor;
+
+impl<'de> Visitor<'de> for BorrowedBitVecVisitor {
+    type Value = BorrowedBitVec<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("struct BitVec with a borrowed buffer")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let len: usize = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let BorrowedBuf(buf) = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+
+        build(len, buf)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut len: Option<usize> = None;
+        let mut buf: Option<BorrowedBuf<'de>> = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Len => {
+                    if len.is_some() {
+                        return Err(Error::duplicate_field("len"));
+                    }
+                    len = Some(map.next_value()?);
+                }
+                Field::Buf => {
+                    if buf.is_some() {
+                        return Err(Error::duplicate_field("buf"));
+                    }
+                    buf = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let len = len.ok_or_else(|| Error::missing_field("len"))?;
+        let BorrowedBuf(buf) = buf.ok_or_else(|| Error::missing_field("buf"))?;
+
+        build(len, buf)
+    }
+}
+
+/// The serialized buffer, accepted only as a byte string borrowed from the
+/// input.
+#[derive(Debug)]
+struct BorrowedBuf<'a>(&'a [Byte]);
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedBuf<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BorrowedBufVisitor)
+    }
+}
+
+#[derive(Debug)]
+struct BorrowedBufVisitor;
+
+impl<'de> Visitor<'de> for BorrowedBufVisitor {
+    type Value = BorrowedBuf<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a borrowed byte string")
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [Byte]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(BorrowedBuf(value))
+    }
+}
+
+/// An iterator over the bits of a [`BorrowedBitVec`].
+///
+/// This struct is created by [`BorrowedBitVec::iter`].
+#[derive(Debug, Clone)]
+pub struct BorrowedIter<'a> {
+    bytes: &'a [Byte],
+    range: Range<usize>,
+}
+
+impl Iterator for BorrowedIter<'_> {
+    type Item = Bit;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        Some(get_bit(self.bytes, index))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = self.range.nth(n)?;
+        Some(get_bit(self.bytes, index))
+    }
+}
+
+impl DoubleEndedIterator for BorrowedIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.range.next_back()?;
+        Some(get_bit(self.bytes, index))
+    }
+}
+
+impl ExactSizeIterator for BorrowedIter<'_> {}
+
+impl FusedIterator for BorrowedIter<'_> {}
+
+fn get_bit(bytes: &[Byte], index: usize) -> Bit {
+    let byte = bytes[index / Byte::BITS as usize];
+    byte & (1 << (Byte::BITS as usize - 1 - index % Byte::BITS as usize)) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+    use serde_test::{
+        Compact, Configure, Token, assert_de_tokens_error, assert_ser_tokens, assert_tokens,
+    };
+
+    #[test]
+    fn test_borrowed() {
+        let bytes = [0b10110000, 0b01111111];
+        let vec = BorrowedBitVec::new(&bytes, 10).unwrap();
+        let expected = bitvec![
+            true, false, true, true, false, false, false, false, false, true
+        ];
+        assert_eq!(vec, expected);
+        assert_eq!(vec.to_bitvec(), expected);
+        assert_eq!(vec.count_ones(), 4);
+        assert_eq!(vec.iter().len(), 10);
+        assert!(vec.iter().rev().eq(expected.iter().rev()));
+        assert_eq!(vec.iter().nth(9), Some(true));
+        assert_eq!(
+            (&vec).into_iter().collect::<Vec<_>>(),
+            Vec::from_iter(&expected)
+        );
+
+        let padded = BorrowedBitVec::new(&[0b10110000, 0b01000000], 10).unwrap();
+        assert_eq!(vec, padded);
+
+        let empty = BorrowedBitVec::new(&[], 0).unwrap();
+        assert_eq!(empty.count_ones(), 0);
+        assert_eq!(empty.to_bitvec(), BitVec::new());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let vec = BorrowedBitVec::new(&[0b10110000, 0b01000000], 10).unwrap();
+        let tokens = [
+            Token::Struct {
+                name: "BitVec",
+                len: 2,
+            },
+            Token::Str("len"),
+            Token::U64(10),
+            Token::Str("buf"),
+            Token::BorrowedBytes(&[0b10110000, 0b01000000]),
+            Token::StructEnd,
+        ];
+        assert_tokens(&vec.compact(), &tokens);
+
+        // A view with set padding bits serializes like the `BitVec` it equals.
+        let padded = BorrowedBitVec::new(&[0b10110000, 0b01111111], 10).unwrap();
+        assert_ser_tokens(&padded.compact(), &tokens);
+        let padded = BorrowedBitVec::new(&[0b10110000, 0b01111111], 16).unwrap();
+        let mut tokens = tokens;
+        tokens[2] = Token::U64(16);
+        tokens[4] = Token::BorrowedBytes(&[0b10110000, 0b01111111]);
+        assert_ser_tokens(&padded.compact(), &tokens);
+
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::U64(10),
+            Token::Bytes(&[0b10110000, 0b01000000]),
+            Token::SeqEnd,
+        ];
+        let message = "invalid type: byte array, expected a borrowed byte string";
+        assert_de_tokens_error::<Compact<BorrowedBitVec<'_>>>(&tokens, message);
+
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::U64(17),
+            Token::BorrowedBytes(&[0b10110000, 0b01000000]),
+            Token::SeqEnd,
+        ];
+        let message = "length 17 exceeds the 2 bytes in the buffer";
+        assert_de_tokens_error::<Compact<BorrowedBitVec<'_>>>(&tokens, message);
+    }
+}
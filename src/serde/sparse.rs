@@ -0,0 +1,240 @@
+//! Encodes a vector either densely or sparsely, whichever is smaller.
+//!
+//! Vectors with fewer than one set bit in [`SPARSE_RATIO`] are written as a
+//! `Sparse` variant holding `(len, deltas)`, where `deltas` are the gaps
+//! between consecutive set bits, the first measured from index 0. Other
+//! vectors are written as a `Dense` variant holding `(len, bytes)`. Either
+//! variant is accepted on input.
+//!
+//! Apply it to a field with `#[serde(with = "bitvek::serde::sparse")]`.
+
+use super::{Buf, BufProxy, Validation, build};
+use crate::BitVec;
+use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+use core::fmt;
+use serde::de::{Deserialize, Deserializer, EnumAccess, Error, Unexpected, VariantAccess, Visitor};
+use serde::ser::Serializer;
+
+/// The number of bits per set bit below which the sparse encoding is used.
+///
+/// Each delta typically takes up to four bytes, as much as 32 dense bits.
+pub const SPARSE_RATIO: usize = 32;
+
+const VARIANTS: &[&str] = &["Dense", "Sparse"];
+
+/// Serializes the vector in the smaller of the two encodings.
+pub fn serialize<A, S>(vec: &BitVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    A: Allocator,
+    S: Serializer,
+{
+    let ones = vec.iter_runs().filter(|&(bit, _, _)| bit);
+    let count: usize = ones.clone().map(|(_, _, len)| len).sum();
+    if count >= vec.len() / SPARSE_RATIO {
+        let dense = (
+            vec.len(),
+            BufProxy {
+                buf: &vec.buf,
+                len: vec.len(),
+            },
+        );
+        return serializer.serialize_newtype_variant("BitVec", 0, "Dense", &dense);
+    }
+
+    let mut deltas = Vec::with_capacity(count);
+    let mut prev = 0;
+    for index in ones.flat_map(|(_, start, len)| start..start + len) {
+        deltas.push(index - prev);
+        prev = index;
+    }
+    let sparse = (vec.len(), deltas);
+    serializer.serialize_newtype_variant("BitVec", 1, "Sparse", &sparse)
+}
+
+/// Deserializes a vector from either encoding, rejecting inconsistent lengths,
+/// repeated indices, indices out of bounds and lengths that cannot be
+/// allocated.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<BitVec, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_enum("BitVec", VARIANTS, EncodingVisitor)
+}
+
+fn from_deltas<E: Error>(len: usize, deltas: Vec<usize>) -> Result<BitVec, E> {
+    let mut vec = BitVec::try_zeroed(len).map_err(Error::custom)?;
+    let mut index: usize = 0;
+    for (position, delta) in deltas.into_iter().enumerate() {
+        if position != 0 && delta == 0 {
+            return Err(Error::custom(format_args!(
+                "delta {position} is zero, repeating an index"
+            )));
+        }
+        index = match index.checked_add(delta) {
+            Some(index) if index < len => index,
+            _ => {
+                return Err(Error::custom(format_args!(
+                    "delta {position} exceeds a length of {len}"
+                )));
+            }
+        };
+        unsafe { vec.set_unchecked(index, true) };
+    }
+    Ok(vec)
+}
+
+#[derive(Debug)]
+struct EncodingVisitor;
+
+impl<'de> Visitor<'de> for EncodingVisitor {
+    type Value = BitVec;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a `Dense` or `Sparse` encoded BitVec")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match data.variant()? {
+            (Encoding::Dense, variant) => {
+                let (len, Buf(buf)) = variant.newtype_variant()?;
+                build(len, buf, Validation::Strict)
+            }
+            (Encoding::Sparse, variant) => {
+                let (len, deltas) = variant.newtype_variant()?;
+                from_deltas(len, deltas)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Encoding {
+    Dense,
+    Sparse,
+}
+
+impl<'de> Deserialize<'de> for Encoding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(EncodingFieldVisitor)
+    }
+}
+
+#[derive(Debug)]
+struct EncodingFieldVisitor;
+
+impl Visitor<'_> for EncodingFieldVisitor {
+    type Value = Encoding;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("`Dense` or `Sparse`")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match value {
+            0 => Ok(Encoding::Dense),
+            1 => Ok(Encoding::Sparse),
+            _ => Err(Error::invalid_value(Unexpected::Unsigned(value), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match value {
+            "Dense" => Ok(Encoding::Dense),
+            "Sparse" => Ok(Encoding::Sparse),
+            _ => Err(Error::unknown_variant(value, VARIANTS)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TryReserveError;
+    use alloc::string::ToString;
+    use serde::{Serialize, Serializer};
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+    #[derive(Debug, PartialEq)]
+    struct Sparse(BitVec);
+
+    impl Serialize for Sparse {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(&self.0, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Sparse {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize(deserializer).map(Sparse)
+        }
+    }
+
+    fn sparse_tokens(len: u64, deltas: &[u64]) -> Vec<Token> {
+        let mut tokens = Vec::from([
+            Token::NewtypeVariant {
+                name: "BitVec",
+                variant: "Sparse",
+            },
+            Token::Tuple { len: 2 },
+            Token::U64(len),
+            Token::Seq {
+                len: Some(deltas.len()),
+            },
+        ]);
+        tokens.extend(deltas.iter().map(|&delta| Token::U64(delta)));
+        tokens.extend([Token::SeqEnd, Token::TupleEnd]);
+        tokens
+    }
+
+    #[test]
+    fn test_sparse() {
+        let vec: BitVec = (0..200).map(|index| matches!(index, 3 | 4 | 150)).collect();
+        let tokens = sparse_tokens(200, &[3, 1, 146]);
+        assert_tokens(&Sparse(vec), &tokens);
+        assert_tokens(&Sparse(BitVec::zeroed(100)), &sparse_tokens(100, &[]));
+
+        let message = "delta 1 is zero, repeating an index";
+        assert_de_tokens_error::<Sparse>(&sparse_tokens(200, &[3, 0]), message);
+        let message = "delta 1 exceeds a length of 200";
+        assert_de_tokens_error::<Sparse>(&sparse_tokens(200, &[3, 197]), message);
+        assert_de_tokens_error::<Sparse>(&sparse_tokens(200, &[3, usize::MAX as u64]), message);
+
+        let message = TryReserveError::AllocError.to_string();
+        assert_de_tokens_error::<Sparse>(&sparse_tokens(1 << 60, &[]), &message);
+    }
+
+    #[test]
+    fn test_dense() {
+        let vec: BitVec = (0..10).map(|index| index % 9 == 0).collect();
+        let tokens = [
+            Token::NewtypeVariant {
+                name: "BitVec",
+                variant: "Dense",
+            },
+            Token::Tuple { len: 2 },
+            Token::U64(10),
+            Token::Bytes(&[0b10000000, 0b01000000]),
+            Token::TupleEnd,
+        ];
+        assert_tokens(&Sparse(vec), &tokens);
+        assert_tokens(&Sparse(BitVec::new()), &{
+            let mut tokens = tokens;
+            tokens[2] = Token::U64(0);
+            tokens[3] = Token::Bytes(&[]);
+            tokens
+        });
+    }
+}
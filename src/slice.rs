@@ -0,0 +1,429 @@
+use crate::primitive::{Bit, Word};
+use crate::{BitVec, Iter, Loc};
+use allocator_api2::alloc::Allocator;
+use core::fmt;
+use core::ops::{Index, Range};
+
+pub(crate) mod chunks;
+
+/// A borrowed view into a contiguous range of bits.
+///
+/// Bit slices are produced by [`BitVec::as_bitslice`] and by the chunked
+/// iterators such as [`BitVec::chunks`].
+#[derive(Clone, Copy)]
+pub struct BitSlice<'a> {
+    // Invariant: `self.start + self.len <= self.buf.len() * Word::BITS`
+    buf: &'a [Word],
+    start: usize,
+    len: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    pub(crate) fn new(buf: &'a [Word], range: Range<usize>) -> Self {
+        let start = range.start;
+        let len = range.len();
+        Self { buf, start, len }
+    }
+
+    /// Returns the number of bits in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// assert_eq!(vec.as_bitslice().len(), 4);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slice contains no bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![];
+    /// assert!(vec.as_bitslice().is_empty());
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// assert!(!vec.as_bitslice().is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at the specified index, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// let slice = vec.chunks(2).nth(1).unwrap();
+    /// assert_eq!(slice.get(0), Some(false));
+    /// assert_eq!(slice.get(2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            None
+        } else {
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
+
+    /// Returns the bit at the specified index, without performing any bounds
+    /// checking.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with an out-of-bounds index is *[undefined behavior]*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// let slice = vec.chunks(2).nth(1).unwrap();
+    /// assert_eq!(unsafe { slice.get_unchecked(0) }, false);
+    /// ```
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> Bit {
+        let loc = Loc::new(self.start + index);
+        let word = unsafe { self.buf.get_unchecked(loc.period) };
+        word.get(loc.offset)
+    }
+
+    /// Returns an iterator over the bits of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// let slice = vec.chunks(3).next().unwrap();
+    /// assert!(slice.iter().eq([true, true, false]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'a> {
+        Iter::new(self.buf, self.range())
+    }
+
+    /// Returns the number of set bits in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, true];
+    /// let slice = vec.chunks(3).next().unwrap();
+    /// assert_eq!(slice.count_ones(), 2);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        unsafe { count_ones(self.buf, self.range()) }
+    }
+
+    /// Returns the number of unset bits in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, true];
+    /// let slice = vec.chunks(3).next().unwrap();
+    /// assert_eq!(slice.count_zeros(), 1);
+    /// ```
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Copies the bits of the slice into a new [`BitVec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// let slice = vec.chunks(3).nth(1).unwrap();
+    /// assert_eq!(slice.to_bitvec(), bitvec![false]);
+    /// ```
+    #[inline]
+    pub fn to_bitvec(&self) -> BitVec {
+        self.iter().collect()
+    }
+
+    pub(crate) fn buf(&self) -> &'a [Word] {
+        self.buf
+    }
+
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.start + self.len
+    }
+}
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns a [`BitSlice`] covering the whole vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false];
+    /// assert_eq!(vec.as_bitslice(), vec);
+    /// ```
+    #[inline]
+    pub fn as_bitslice(&self) -> BitSlice<'_> {
+        BitSlice::new(&self.buf, 0..self.len)
+    }
+
+    /// Returns the number of set bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, true];
+    /// assert_eq!(vec.count_ones(), 3);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        unsafe { count_ones(&self.buf, 0..self.len) }
+    }
+
+    /// Returns the number of unset bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, true];
+    /// assert_eq!(vec.count_zeros(), 1);
+    /// ```
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+}
+
+impl<'a> IntoIterator for BitSlice<'a> {
+    type Item = Bit;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &BitSlice<'a> {
+    type Item = Bit;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Index<usize> for BitSlice<'_> {
+    type Output = Bit;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            None => panic!("index out of bounds"),
+            Some(false) => &false,
+            Some(true) => &true,
+        }
+    }
+}
+
+impl fmt::Debug for BitSlice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq<BitSlice<'_>> for BitSlice<'_> {
+    #[inline]
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BitSlice<'_> {}
+
+impl<A: Allocator> PartialEq<BitVec<A>> for BitSlice<'_> {
+    #[inline]
+    fn eq(&self, other: &BitVec<A>) -> bool {
+        *self == other.as_bitslice()
+    }
+}
+
+impl<A: Allocator> PartialEq<BitSlice<'_>> for BitVec<A> {
+    #[inline]
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        self.as_bitslice() == *other
+    }
+}
+
+/// Reads the bits in `range` as a big-endian unsigned integer, with the first
+/// bit being the most significant.
+///
+/// # Safety
+///
+/// The range must lie within `buf` and span at most 128 bits.
+pub(crate) unsafe fn load_bits(buf: &[Word], range: Range<usize>) -> u128 {
+    let mut value = 0;
+    let mut index = range.start;
+    while index < range.end {
+        let loc = Loc::new(index);
+        let count = (Word::BITS - loc.offset).min(range.end - index);
+        let word = unsafe { buf.get_unchecked(loc.period) };
+        value = value << count | word.extract(loc.offset, count) as u128;
+        index += count;
+    }
+    value
+}
+
+/// Counts the set bits in `range`.
+///
+/// # Safety
+///
+/// The range must lie within `buf`.
+pub(crate) unsafe fn count_ones(buf: &[Word], range: Range<usize>) -> usize {
+    let mut ones = 0;
+    let mut index = range.start;
+    while index < range.end {
+        let loc = Loc::new(index);
+        let count = (Word::BITS - loc.offset).min(range.end - index);
+        let word = unsafe { buf.get_unchecked(loc.period) };
+        ones += (*word & Word::span(loc.offset, count)).count_ones();
+        index += count;
+    }
+    ones
+}
+
+/// Writes the lowest `range.len()` bits of `value` into `range`, with the first
+/// bit being the most significant.
+///
+/// # Safety
+///
+/// The range must lie within `buf` and span at most 128 bits.
+pub(crate) unsafe fn store_bits(buf: &mut [Word], range: Range<usize>, value: u128) {
+    let mut index = range.start;
+    while index < range.end {
+        let loc = Loc::new(index);
+        let count = (Word::BITS - loc.offset).min(range.end - index);
+        let bits = (value >> (range.end - index - count)) as usize;
+        let word = unsafe { buf.get_unchecked_mut(loc.period) };
+        word.insert(loc.offset, count, bits);
+        index += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+
+    #[test]
+    fn test_bitslice() {
+        let mut vec = BitVec::from_iter((0..Word::BITS * 2).map(|index| index % 3 == 0));
+        vec.push_unused_word();
+
+        let slice = BitSlice::new(&vec.buf, 1..Word::BITS + 2);
+        assert_eq!(slice.len(), Word::BITS + 1);
+        for index in 0..slice.len() {
+            assert_eq!(slice.get(index), vec.get(index + 1));
+            assert_eq!(slice[index], vec[index + 1]);
+        }
+        assert_eq!(slice.get(slice.len()), None);
+        assert!(slice.iter().eq(vec.iter().skip(1).take(Word::BITS + 1)));
+        assert_eq!(slice.to_bitvec(), slice);
+
+        assert_eq!(vec.as_bitslice(), vec);
+        assert_ne!(BitSlice::new(&vec.buf, 0..3), bitvec![true, false, true]);
+        assert_eq!(BitSlice::new(&vec.buf, 3..6), BitSlice::new(&vec.buf, 0..3));
+    }
+
+    #[test]
+    fn test_count() {
+        let mut vec = BitVec::from_iter((0..Word::BITS * 2 + 5).map(|index| index % 3 == 0));
+        vec.push(true);
+        vec.pop();
+        vec.push_unused_word();
+        for range in [
+            0..0,
+            0..vec.len(),
+            1..Word::BITS + 2,
+            Word::BITS..Word::BITS + 3,
+        ] {
+            let slice = BitSlice::new(&vec.buf, range.clone());
+            let ones = vec
+                .iter()
+                .skip(range.start)
+                .take(range.len())
+                .filter(|bit| *bit);
+            assert_eq!(slice.count_ones(), ones.count());
+            assert_eq!(slice.count_ones() + slice.count_zeros(), range.len());
+        }
+        assert_eq!(vec.count_ones(), vec.len().div_ceil(3));
+        assert_eq!(vec.count_zeros(), vec.len() - vec.count_ones());
+    }
+
+    #[test]
+    fn test_load_bits() {
+        let vec = bitvec![0b10110011, 0b01010101, 0b11110000];
+        assert_eq!(unsafe { load_bits(&vec.buf, 0..8) }, 0b10110011);
+        assert_eq!(unsafe { load_bits(&vec.buf, 4..12) }, 0b00110101);
+        assert_eq!(unsafe { load_bits(&vec.buf, 6..7) }, 1);
+        assert_eq!(unsafe { load_bits(&vec.buf, 6..6) }, 0);
+
+        let vec = BitVec::from_iter((0..130).map(|index| index % 2 == 0));
+        let expected = u128::MAX / 3 * 2;
+        assert_eq!(unsafe { load_bits(&vec.buf, 0..128) }, expected);
+        assert_eq!(unsafe { load_bits(&vec.buf, 1..129) }, expected >> 1);
+        assert_eq!(
+            unsafe { load_bits(&vec.buf, 3..67) },
+            (expected >> 1) as u64 as u128
+        );
+    }
+
+    #[test]
+    fn test_store_bits() {
+        let mut vec = BitVec::from_iter([false; 130]);
+        unsafe { store_bits(&mut vec.buf, 3..11, 0b1_10110011) };
+        assert_eq!(unsafe { load_bits(&vec.buf, 0..16) }, 0b00010110_01100000);
+
+        for range in [0..128, 1..129, 2..66, 60..70] {
+            let mut vec = BitVec::from_iter([true; 130]);
+            let value = u128::MAX / 3;
+            unsafe { store_bits(&mut vec.buf, range.clone(), value) };
+            let mask = u128::MAX >> (128 - range.len());
+            assert_eq!(unsafe { load_bits(&vec.buf, range.clone()) }, value & mask);
+            assert!(vec.iter().take(range.start).all(|bit| bit));
+            assert!(vec.iter().skip(range.end).all(|bit| bit));
+        }
+    }
+}
@@ -0,0 +1,569 @@
+use super::{BitSlice, load_bits};
+use crate::BitVec;
+use crate::primitive::Word;
+use allocator_api2::alloc::Allocator;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+impl<A: Allocator> BitVec<A> {
+    /// Returns an iterator over `chunk_size` bits of the vector at a time,
+    /// starting at the beginning of the vector.
+    ///
+    /// The chunks do not overlap. If `chunk_size` does not divide the length of
+    /// the vector, the last chunk will be shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false, true];
+    /// let mut iter = vec.chunks(2);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true, true]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![false, false]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true]);
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let buf = &self.buf;
+        let range = 0..self.len;
+        Chunks {
+            buf,
+            range,
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over `chunk_size` bits of the vector at a time,
+    /// starting at the beginning of the vector.
+    ///
+    /// The chunks do not overlap. If `chunk_size` does not divide the length of
+    /// the vector, the last up to `chunk_size - 1` bits are omitted and can be
+    /// retrieved from [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false, true];
+    /// let mut iter = vec.chunks_exact(2);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true, true]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![false, false]);
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(iter.remainder(), bitvec![true]);
+    /// ```
+    #[inline]
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ChunksExact::new(&self.buf, 0..self.len, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` bits of the vector at a time,
+    /// starting at the end of the vector.
+    ///
+    /// The chunks do not overlap. If `chunk_size` does not divide the length of
+    /// the vector, the last chunk will be shorter and contain the leading bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false, true];
+    /// let mut iter = vec.rchunks(2);
+    /// assert_eq!(iter.next().unwrap(), bitvec![false, true]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true, false]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true]);
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn rchunks(&self, chunk_size: usize) -> RChunks<'_> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let buf = &self.buf;
+        let range = 0..self.len;
+        RChunks {
+            buf,
+            range,
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over all contiguous windows of `size` bits. The
+    /// windows overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false];
+    /// let mut iter = vec.windows(2);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true, true]);
+    /// assert_eq!(iter.next().unwrap(), bitvec![true, false]);
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn windows(&self, size: usize) -> Windows<'_> {
+        assert!(size != 0, "window size must be non-zero");
+        let buf = &self.buf;
+        let range = 0..self.len;
+        Windows { buf, range, size }
+    }
+
+    /// Returns an iterator that reads consecutive `width`-bit fields of the
+    /// vector as [`u64`] values, with the first bit of each field being the most
+    /// significant.
+    ///
+    /// Trailing bits that do not fill a whole field are skipped, as with
+    /// [`chunks_exact`](BitVec::chunks_exact).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero or greater than 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![0b10110011, 0b01010101];
+    /// let fields: Vec<u64> = vec.iter_u64(5).collect();
+    /// assert_eq!(fields, [0b10110, 0b01101, 0b01010]);
+    /// ```
+    #[inline]
+    pub fn iter_u64(&self, width: usize) -> IterU64<'_> {
+        assert!(
+            (1..=u64::BITS as usize).contains(&width),
+            "width must be between 1 and 64"
+        );
+        let chunks = ChunksExact::new(&self.buf, 0..self.len, width);
+        IterU64 { chunks }
+    }
+
+    /// Returns an iterator that reads consecutive `width`-bit fields of the
+    /// vector as [`u128`] values, with the first bit of each field being the
+    /// most significant.
+    ///
+    /// Trailing bits that do not fill a whole field are skipped, as with
+    /// [`chunks_exact`](BitVec::chunks_exact).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero or greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![0b10110011, 0b01010101];
+    /// let fields: Vec<u128> = vec.iter_u128(12).collect();
+    /// assert_eq!(fields, [0b101100110101]);
+    /// ```
+    #[inline]
+    pub fn iter_u128(&self, width: usize) -> IterU128<'_> {
+        assert!(
+            (1..=u128::BITS as usize).contains(&width),
+            "width must be between 1 and 128"
+        );
+        let chunks = ChunksExact::new(&self.buf, 0..self.len, width);
+        IterU128 { chunks }
+    }
+}
+
+/// An iterator over a [`BitVec`] in non-overlapping chunks, starting at the
+/// beginning of the vector.
+///
+/// This struct is created by [`BitVec::chunks`].
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    buf: &'a [Word],
+    range: Range<usize>,
+    chunk_size: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = BitSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let start = self.range.start;
+        let end = start + self.chunk_size.min(self.range.len());
+        self.range.start = end;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.range.len().div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Chunks<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let end = self.range.end;
+        let start = end - last_chunk_len(self.range.len(), self.chunk_size);
+        self.range.end = start;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+}
+
+impl ExactSizeIterator for Chunks<'_> {}
+
+impl FusedIterator for Chunks<'_> {}
+
+/// An iterator over a [`BitVec`] in non-overlapping chunks of exactly
+/// `chunk_size` bits, starting at the beginning of the vector.
+///
+/// This struct is created by [`BitVec::chunks_exact`].
+#[derive(Debug, Clone)]
+pub struct ChunksExact<'a> {
+    buf: &'a [Word],
+    range: Range<usize>,
+    rem: Range<usize>,
+    chunk_size: usize,
+}
+
+impl<'a> ChunksExact<'a> {
+    fn new(buf: &'a [Word], range: Range<usize>, chunk_size: usize) -> Self {
+        let split = range.end - range.len() % chunk_size;
+        let rem = split..range.end;
+        let range = range.start..split;
+        Self {
+            buf,
+            range,
+            rem,
+            chunk_size,
+        }
+    }
+
+    /// Returns the remainder of the original vector that is not going to be
+    /// returned by the iterator. It holds at most `chunk_size - 1` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::bitvec;
+    ///
+    /// let vec = bitvec![true, true, false, false, true];
+    /// assert_eq!(vec.chunks_exact(2).remainder(), bitvec![true]);
+    /// assert!(vec.chunks_exact(5).remainder().is_empty());
+    /// ```
+    #[inline]
+    pub fn remainder(&self) -> BitSlice<'a> {
+        BitSlice::new(self.buf, self.rem.clone())
+    }
+}
+
+impl<'a> Iterator for ChunksExact<'a> {
+    type Item = BitSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let start = self.range.start;
+        let end = start + self.chunk_size;
+        self.range.start = end;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.range.len() / self.chunk_size;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for ChunksExact<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let end = self.range.end;
+        let start = end - self.chunk_size;
+        self.range.end = start;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+}
+
+impl ExactSizeIterator for ChunksExact<'_> {}
+
+impl FusedIterator for ChunksExact<'_> {}
+
+/// An iterator over a [`BitVec`] in non-overlapping chunks, starting at the
+/// end of the vector.
+///
+/// This struct is created by [`BitVec::rchunks`].
+#[derive(Debug, Clone)]
+pub struct RChunks<'a> {
+    buf: &'a [Word],
+    range: Range<usize>,
+    chunk_size: usize,
+}
+
+impl<'a> Iterator for RChunks<'a> {
+    type Item = BitSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let end = self.range.end;
+        let start = end - self.chunk_size.min(self.range.len());
+        self.range.end = start;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.range.len().div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for RChunks<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let start = self.range.start;
+        let end = start + last_chunk_len(self.range.len(), self.chunk_size);
+        self.range.start = end;
+        Some(BitSlice::new(self.buf, start..end))
+    }
+}
+
+impl ExactSizeIterator for RChunks<'_> {}
+
+impl FusedIterator for RChunks<'_> {}
+
+/// An iterator over overlapping windows of a [`BitVec`].
+///
+/// This struct is created by [`BitVec::windows`].
+#[derive(Debug, Clone)]
+pub struct Windows<'a> {
+    buf: &'a [Word],
+    range: Range<usize>,
+    size: usize,
+}
+
+impl<'a> Iterator for Windows<'a> {
+    type Item = BitSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.len() < self.size {
+            return None;
+        }
+        let start = self.range.start;
+        self.range.start += 1;
+        Some(BitSlice::new(self.buf, start..start + self.size))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.range.len() + 1).saturating_sub(self.size);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Windows<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.len() < self.size {
+            return None;
+        }
+        let end = self.range.end;
+        self.range.end -= 1;
+        Some(BitSlice::new(self.buf, end - self.size..end))
+    }
+}
+
+impl ExactSizeIterator for Windows<'_> {}
+
+impl FusedIterator for Windows<'_> {}
+
+macro_rules! iter_uint {
+    ($name:ident, $uint:ty, $method:literal) => {
+        #[doc = concat!("An iterator that reads fixed-width fields of a [`BitVec`] as [`", stringify!($uint), "`] values.")]
+        ///
+        #[doc = concat!("This struct is created by [`BitVec::", $method, "`].")]
+        #[derive(Debug, Clone)]
+        pub struct $name<'a> {
+            chunks: ChunksExact<'a>,
+        }
+
+        impl Iterator for $name<'_> {
+            type Item = $uint;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let slice = self.chunks.next()?;
+                Some(unsafe { load_bits(slice.buf, slice.range()) } as $uint)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.chunks.size_hint()
+            }
+        }
+
+        impl DoubleEndedIterator for $name<'_> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let slice = self.chunks.next_back()?;
+                Some(unsafe { load_bits(slice.buf, slice.range()) } as $uint)
+            }
+        }
+
+        impl ExactSizeIterator for $name<'_> {}
+
+        impl FusedIterator for $name<'_> {}
+    };
+}
+
+iter_uint!(IterU64, u64, "iter_u64");
+iter_uint!(IterU128, u128, "iter_u128");
+
+/// Returns the length of the shorter chunk at the far end of `len` bits split
+/// into chunks of `chunk_size`.
+fn last_chunk_len(len: usize, chunk_size: usize) -> usize {
+    match len % chunk_size {
+        0 => chunk_size,
+        rem => rem,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern;
+    use alloc::vec::Vec;
+
+    const LEN: usize = Word::BITS * 2 + 5;
+
+    fn to_vecs<'a>(iter: impl Iterator<Item = BitSlice<'a>>) -> Vec<Vec<bool>> {
+        iter.map(|slice| slice.iter().collect()).collect()
+    }
+
+    #[test]
+    fn test_chunks() {
+        let (vec, bits) = pattern(LEN);
+
+        for size in [1, 3, Word::BITS, Word::BITS + 1, LEN, LEN + 1] {
+            let expected: Vec<Vec<bool>> = bits.chunks(size).map(<[_]>::to_vec).collect();
+            assert_eq!(vec.chunks(size).len(), expected.len());
+            assert_eq!(to_vecs(vec.chunks(size)), expected);
+            assert_eq!(
+                to_vecs(vec.chunks(size).rev()),
+                expected.into_iter().rev().collect::<Vec<_>>()
+            );
+
+            let expected: Vec<Vec<bool>> = bits.chunks_exact(size).map(<[_]>::to_vec).collect();
+            let iter = vec.chunks_exact(size);
+            assert!(
+                iter.remainder()
+                    .iter()
+                    .eq(bits.chunks_exact(size).remainder().iter().copied())
+            );
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(to_vecs(iter), expected);
+            assert_eq!(
+                to_vecs(vec.chunks_exact(size).rev()),
+                expected.into_iter().rev().collect::<Vec<_>>()
+            );
+
+            let expected: Vec<Vec<bool>> = bits.rchunks(size).map(<[_]>::to_vec).collect();
+            assert_eq!(vec.rchunks(size).len(), expected.len());
+            assert_eq!(to_vecs(vec.rchunks(size)), expected);
+            assert_eq!(
+                to_vecs(vec.rchunks(size).rev()),
+                expected.into_iter().rev().collect::<Vec<_>>()
+            );
+
+            let expected: Vec<Vec<bool>> = bits.windows(size).map(<[_]>::to_vec).collect();
+            assert_eq!(vec.windows(size).len(), expected.len());
+            assert_eq!(to_vecs(vec.windows(size)), expected);
+            assert_eq!(
+                to_vecs(vec.windows(size).rev()),
+                expected.into_iter().rev().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_fail() {
+        BitVec::new().chunks(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn test_windows_fail() {
+        BitVec::new().windows(0);
+    }
+
+    #[test]
+    fn test_iter_uint() {
+        fn value(bits: &[bool]) -> u128 {
+            bits.iter().fold(0, |acc, bit| acc << 1 | *bit as u128)
+        }
+
+        let (vec, bits) = pattern(LEN);
+
+        for width in [1, 7, Word::BITS - 1, Word::BITS, 64] {
+            let expected: Vec<u64> = bits
+                .chunks_exact(width)
+                .map(|bits| value(bits) as u64)
+                .collect();
+            assert_eq!(vec.iter_u64(width).collect::<Vec<_>>(), expected);
+            assert_eq!(
+                vec.iter_u64(width).rev().collect::<Vec<_>>(),
+                expected.into_iter().rev().collect::<Vec<_>>()
+            );
+        }
+
+        for width in [1, 65, 100, 128] {
+            let expected: Vec<u128> = bits.chunks_exact(width).map(value).collect();
+            assert_eq!(vec.iter_u128(width).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "width must be between 1 and 64")]
+    fn test_iter_u64_fail() {
+        BitVec::new().iter_u64(65);
+    }
+}
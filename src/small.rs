@@ -0,0 +1,1733 @@
+use crate::iter::Cursor;
+use crate::primitive::{Bit, Byte, Word};
+use crate::slice::count_ones;
+use crate::{BitSlice, BitVec, Integer, Iter, Loc, Matches, Runs, TryReserveError};
+use crate::{
+    checked_range, eq_bits, field, hash_bits, range, reserve_error, resolve_range, search,
+};
+use alloc::boxed::Box;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+#[cfg(feature = "nightly")]
+use core::num::NonZero;
+use core::ops::{Index, RangeBounds};
+
+mod bitwise;
+
+/// A bit vector that stores up to `N` words inline.
+///
+/// It behaves like a [`BitVec`], except that it does not allocate until it
+/// needs to hold more than `N * usize::BITS` bits. Once spilled to the heap, it
+/// only moves back inline when explicitly shrunk.
+///
+/// # Examples
+///
+/// ```
+/// use bitvek::SmallBitVec;
+///
+/// let mut vec = SmallBitVec::<2>::new();
+/// vec.extend([true; 128]);
+/// assert!(!vec.spilled());
+///
+/// vec.push(false);
+/// assert!(vec.spilled());
+/// ```
+pub struct SmallBitVec<const N: usize> {
+    // Invariant: `self.buf_used() <= self.buf.as_slice().len()`
+    len: usize,
+    buf: Buf<N>,
+}
+
+#[derive(Clone)]
+enum Buf<const N: usize> {
+    Inline([Word; N]),
+    Heap(Vec<Word>),
+}
+
+impl<const N: usize> Buf<N> {
+    fn capacity(&self) -> usize {
+        match self {
+            Self::Inline(_) => N,
+            Self::Heap(buf) => buf.capacity(),
+        }
+    }
+
+    fn as_slice(&self) -> &[Word] {
+        match self {
+            Self::Inline(buf) => buf,
+            Self::Heap(buf) => buf,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Word] {
+        match self {
+            Self::Inline(buf) => buf,
+            Self::Heap(buf) => buf,
+        }
+    }
+
+    /// Builds a buffer from exactly `buf_len` words, preferring inline storage.
+    fn from_words<I>(words: I, buf_len: usize) -> Self
+    where
+        I: Iterator<Item = Word>,
+    {
+        if buf_len <= N {
+            let mut buf = [Word::CLEAR; N];
+            buf.iter_mut().zip(words).for_each(|(dst, src)| *dst = src);
+            Self::Inline(buf)
+        } else {
+            Self::Heap(words.take(buf_len).collect())
+        }
+    }
+}
+
+impl<const N: usize> SmallBitVec<N> {
+    /// Returns the total number of bits the vector can hold without reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<2>::new();
+    /// assert_eq!(vec.capacity(), usize::BITS as usize * 2);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity().saturating_mul(Word::BITS)
+    }
+
+    /// Returns the number of bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<2>::from([true, true, false, false]);
+    /// assert_eq!(vec.len(), 4);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<2>::new();
+    /// assert!(vec.is_empty());
+    ///
+    /// let vec = SmallBitVec::<2>::from([true, true, false, false]);
+    /// assert!(!vec.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the bits have been moved to the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true; 64]);
+    /// assert!(!vec.spilled());
+    ///
+    /// let vec = SmallBitVec::<1>::from([true; 65]);
+    /// assert!(vec.spilled());
+    /// ```
+    #[inline]
+    pub const fn spilled(&self) -> bool {
+        matches!(self.buf, Buf::Heap(_))
+    }
+
+    /// Returns the number of words used to store the bits in the vector.
+    const fn buf_used(&self) -> usize {
+        self.len.div_ceil(Word::BITS)
+    }
+}
+
+impl<const N: usize> SmallBitVec<N> {
+    /// Creates a new, empty [`SmallBitVec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<2>::new();
+    /// assert_eq!(vec.len(), 0);
+    /// assert!(!vec.spilled());
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        let len = 0;
+        let buf = Buf::Inline([Word::CLEAR; N]);
+        Self { len, buf }
+    }
+
+    /// Creates a new, empty [`SmallBitVec`] with the specified capacity.
+    ///
+    /// The vector will be able to hold at least `capacity` bits without
+    /// reallocating. If `capacity` fits inline, the vector will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::with_capacity(10);
+    /// assert!(!vec.spilled());
+    ///
+    /// let vec = SmallBitVec::<1>::with_capacity(100);
+    /// assert!(vec.spilled());
+    /// assert!(vec.capacity() >= 100);
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let len = 0;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        let buf = if buf_capacity <= N {
+            Buf::Inline([Word::CLEAR; N])
+        } else {
+            Buf::Heap(Vec::with_capacity(buf_capacity))
+        };
+        Self { len, buf }
+    }
+
+    /// Creates a new, empty [`SmallBitVec`] with the specified capacity, returning
+    /// an error instead of aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::try_with_capacity(100).unwrap();
+    /// assert!(vec.spilled());
+    /// assert!(vec.capacity() >= 100);
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut vec = Self::new();
+        vec.try_reserve(capacity)?;
+        Ok(vec)
+    }
+}
+
+impl<const N: usize> SmallBitVec<N> {
+    /// Reserves capacity for at least `additional` more bits to be inserted in the
+    /// given [`SmallBitVec`], spilling to the heap if the inline storage is not
+    /// large enough.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the required capacity exceeds `usize::MAX` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.reserve(6);
+    /// assert!(!vec.spilled());
+    ///
+    /// vec.reserve(100);
+    /// assert!(vec.spilled());
+    /// assert!(vec.capacity() >= 104);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        let capacity = self.len.checked_add(additional).expect("capacity overflow");
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        match &mut self.buf {
+            Buf::Inline(buf) => {
+                if buf_capacity > N {
+                    let buf_used = self.len.div_ceil(Word::BITS);
+                    let mut heap = Vec::with_capacity(buf_capacity);
+                    heap.extend_from_slice(unsafe { buf.get_unchecked(..buf_used) });
+                    self.buf = Buf::Heap(heap);
+                }
+            }
+            Buf::Heap(buf) => {
+                if let Some(buf_additional) = buf_capacity.checked_sub(buf.len()) {
+                    buf.reserve(buf_additional);
+                }
+            }
+        }
+        self
+    }
+
+    /// Tries to reserve capacity for at least `additional` more bits to be inserted
+    /// in the given [`SmallBitVec`]. This is the fallible counterpart of
+    /// [`reserve`](SmallBitVec::reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, TryReserveError};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert!(vec.try_reserve(100).is_ok());
+    /// assert!(vec.capacity() >= 104);
+    ///
+    /// assert_eq!(
+    ///     vec.try_reserve(usize::MAX).unwrap_err(),
+    ///     TryReserveError::CapacityOverflow,
+    /// );
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<&mut Self, TryReserveError> {
+        let capacity = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let buf_capacity = capacity.div_ceil(Word::BITS);
+        match &mut self.buf {
+            Buf::Inline(buf) => {
+                if buf_capacity > N {
+                    let buf_used = self.len.div_ceil(Word::BITS);
+                    let mut heap = Vec::new();
+                    heap.try_reserve_exact(buf_capacity)
+                        .map_err(|_| reserve_error(buf_capacity))?;
+                    heap.extend_from_slice(unsafe { buf.get_unchecked(..buf_used) });
+                    self.buf = Buf::Heap(heap);
+                }
+            }
+            Buf::Heap(buf) => {
+                if let Some(buf_additional) = buf_capacity.checked_sub(buf.len()) {
+                    buf.try_reserve(buf_additional)
+                        .map_err(|_| reserve_error(buf_capacity))?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Shrinks the capacity of the vector as much as possible, moving the bits
+    /// back inline if they fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// let unchanged = vec.clone();
+    ///
+    /// vec.reserve(100);
+    /// assert!(vec.spilled());
+    ///
+    /// vec.shrink_to_fit();
+    /// assert!(!vec.spilled());
+    /// assert_eq!(vec, unchanged);
+    /// ```
+    pub fn shrink_to_fit(&mut self) -> &mut Self {
+        self.shrink_to(0)
+    }
+
+    /// Shrinks the capacity of the vector with a lower bound, moving the bits
+    /// back inline if the bound allows it.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// let unchanged = vec.clone();
+    ///
+    /// vec.reserve(200);
+    /// vec.shrink_to(100);
+    /// assert!(vec.spilled());
+    /// assert!(vec.capacity() >= 100);
+    /// assert_eq!(vec, unchanged);
+    ///
+    /// vec.shrink_to(0);
+    /// assert!(!vec.spilled());
+    /// assert_eq!(vec, unchanged);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) -> &mut Self {
+        let buf_used = self.buf_used();
+        let Buf::Heap(buf) = &mut self.buf else {
+            return self;
+        };
+        let buf_min_capacity = min_capacity.div_ceil(Word::BITS);
+        let buf_new_len = buf_used.max(buf_min_capacity);
+        if buf_new_len <= N {
+            let words = buf.iter().copied();
+            self.buf = Buf::from_words(words, buf_used);
+        } else if buf_min_capacity < buf.len() {
+            buf.truncate(buf_new_len);
+            buf.shrink_to_fit();
+        } else if buf_min_capacity < buf.capacity() {
+            buf.shrink_to(buf_min_capacity);
+        }
+        self
+    }
+
+    /// Returns the bit at the specified index, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert_eq!(vec.get(3), Some(false));
+    /// assert_eq!(vec.get(4), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            None
+        } else {
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
+
+    /// Returns the bit at the specified index, without performing any bounds
+    /// checking.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with an out-of-bounds index is *[undefined behavior]*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert_eq!(unsafe { vec.get_unchecked(3) }, false);
+    /// ```
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> Bit {
+        let loc = Loc::new(index);
+        let word = unsafe { self.buf.as_slice().get_unchecked(loc.period) };
+        word.get(loc.offset)
+    }
+
+    /// Sets the bit at the specified index to the specified value, if in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert!(vec.set(2, true).is_some());
+    /// assert!(vec.set(3, true).is_some());
+    /// assert!(vec.set(4, true).is_none());
+    /// assert_eq!(vec, SmallBitVec::<1>::from([true; 4]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn set(&mut self, index: usize, value: Bit) -> Option<&mut Self> {
+        if index >= self.len {
+            None
+        } else {
+            Some(unsafe { self.set_unchecked(index, value) })
+        }
+    }
+
+    /// Sets the bit at the specified index to the specified value, without
+    /// performing any bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with an out-of-bounds index is *[undefined behavior]*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// unsafe {
+    ///     vec.set_unchecked(2, true);
+    ///     vec.set_unchecked(3, true);
+    /// }
+    /// assert_eq!(vec, SmallBitVec::<1>::from([true; 4]));
+    /// ```
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[inline]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: Bit) -> &mut Self {
+        let loc = Loc::new(index);
+        let word = unsafe { self.buf.as_mut_slice().get_unchecked_mut(loc.period) };
+        word.set(loc.offset, value);
+        self
+    }
+
+    /// Appends a bit to the back of the vector, spilling to the heap if the inline
+    /// storage is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the required capacity exceeds `usize::MAX` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.push(true);
+    /// assert_eq!(vec, SmallBitVec::<1>::from([true, true, false, false, true]));
+    /// ```
+    pub fn push(&mut self, value: Bit) -> &mut Self {
+        assert!(self.len != usize::MAX, "capacity overflow");
+        let loc = Loc::new(self.len);
+        if loc.period < self.buf.as_slice().len() {
+            let word = unsafe { self.buf.as_mut_slice().get_unchecked_mut(loc.period) };
+            word.set(loc.offset, value);
+        } else {
+            let word = if value {
+                Word::MSB_SET
+            } else {
+                Word::MSB_CLEAR
+            };
+            match &mut self.buf {
+                Buf::Inline(buf) => {
+                    let mut heap = Vec::with_capacity((N * 2).max(1));
+                    heap.extend_from_slice(buf);
+                    heap.push(word);
+                    self.buf = Buf::Heap(heap);
+                }
+                Buf::Heap(buf) => buf.push(word),
+            }
+        }
+        self.len += 1;
+        self
+    }
+
+    /// Appends a bit to the back of the vector, returning an error instead of
+    /// panicking or aborting if the vector cannot grow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure. The vector is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.try_push(true).unwrap();
+    /// assert_eq!(vec, SmallBitVec::<1>::from([true, true, false, false, true]));
+    /// ```
+    pub fn try_push(&mut self, value: Bit) -> Result<&mut Self, TryReserveError> {
+        self.try_reserve(1)?;
+        Ok(self.push(value))
+    }
+
+    /// Appends all bits of an iterator to the back of the vector, returning an
+    /// error instead of panicking or aborting if the vector cannot grow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the required capacity exceeds `usize::MAX` bits, or if
+    /// the allocator reports a failure. Bits appended before the failure are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true]);
+    /// vec.try_extend([false, false]).unwrap();
+    /// assert_eq!(vec, SmallBitVec::<1>::from([true, true, false, false]));
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<&mut Self, TryReserveError>
+    where
+        I: IntoIterator<Item = Bit>,
+    {
+        let iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        self.try_reserve(additional)?;
+        for value in iter {
+            self.try_push(value)?;
+        }
+        Ok(self)
+    }
+
+    /// Removes the last bit from the vector and returns it, or `None` if the vector
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, false]);
+    /// assert_eq!(vec.pop(), Some(false));
+    /// assert_eq!(vec.pop(), Some(true));
+    /// assert_eq!(vec.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<Bit> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.get_unchecked(self.len) })
+    }
+
+    /// Returns an iterator over the bits of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// let mut iter = vec.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(true));
+    /// assert_eq!(iter.next_back(), Some(false));
+    /// assert_eq!(iter.next(), Some(true));
+    /// assert_eq!(iter.next_back(), Some(false));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(self.buf.as_slice(), 0..self.len)
+    }
+
+    /// Returns a [`BitSlice`] viewing all bits of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert_eq!(vec.as_bitslice(), bitvec![true, true, false, false]);
+    /// ```
+    #[inline]
+    pub fn as_bitslice(&self) -> BitSlice<'_> {
+        BitSlice::new(self.buf.as_slice(), 0..self.len)
+    }
+
+    /// Returns the number of set bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, true]);
+    /// assert_eq!(vec.count_ones(), 3);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        unsafe { count_ones(self.buf.as_slice(), 0..self.len) }
+    }
+
+    /// Returns the number of unset bits in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, true]);
+    /// assert_eq!(vec.count_zeros(), 1);
+    /// ```
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Sets all bits of the vector to the specified value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.fill(true);
+    /// assert_eq!(vec, bitvec![true; 4]);
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: Bit) -> &mut Self {
+        self.fill_range(.., value)
+    }
+
+    /// Sets the bits in the specified range to the specified value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.fill_range(1..3, false);
+    /// assert_eq!(vec, bitvec![true, false, false, false]);
+    /// ```
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::fill_range(self.buf.as_mut_slice(), range, value) };
+        self
+    }
+
+    /// Sets the bits in the specified range to the specified value, if in
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// assert!(vec.set_range(2.., true).is_some());
+    /// assert!(vec.set_range(3..5, false).is_none());
+    /// assert_eq!(vec, bitvec![true; 4]);
+    /// ```
+    #[must_use]
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: Bit) -> Option<&mut Self> {
+        let range = checked_range(range, self.len)?;
+        unsafe { range::fill_range(self.buf.as_mut_slice(), range, value) };
+        Some(self)
+    }
+
+    /// Flips the bits in the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.toggle_range(1..3);
+    /// assert_eq!(vec, bitvec![true, false, true, false]);
+    /// ```
+    pub fn toggle_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::toggle_range(self.buf.as_mut_slice(), range) };
+        self
+    }
+
+    /// Copies the bits in `src` to the position starting at `dest`. The two
+    /// ranges may overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds, or if the destination range ends past
+    /// the end of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false, false]);
+    /// vec.copy_within(0..3, 2);
+    /// assert_eq!(vec, bitvec![true, true, true, true, false]);
+    /// ```
+    pub fn copy_within(&mut self, src: impl RangeBounds<usize>, dest: usize) -> &mut Self {
+        let src = resolve_range(src, self.len);
+        assert!(dest <= self.len - src.len(), "dest is out of bounds");
+        unsafe { range::copy_within(self.buf.as_mut_slice(), src, dest) };
+        self
+    }
+
+    /// Swaps two bits in the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+    /// vec.swap(0, 3);
+    /// assert_eq!(vec, bitvec![false, true, false, true]);
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) -> &mut Self {
+        assert!(a < self.len && b < self.len, "index out of bounds");
+        unsafe { range::swap(self.buf.as_mut_slice(), a, b) };
+        self
+    }
+
+    /// Reverses the order of bits in the vector, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, true, false]);
+    /// vec.reverse();
+    /// assert_eq!(vec, bitvec![false, true, false, true, true]);
+    /// ```
+    #[inline]
+    pub fn reverse(&mut self) -> &mut Self {
+        unsafe { range::reverse(self.buf.as_mut_slice(), self.len) };
+        self
+    }
+
+    /// Reverses the order of bits in the specified range, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([true, true, false, true, false]);
+    /// vec.reverse_range(..3);
+    /// assert_eq!(vec, bitvec![false, true, true, true, false]);
+    /// ```
+    pub fn reverse_range(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let range = resolve_range(range, self.len);
+        unsafe { range::reverse_range(self.buf.as_mut_slice(), range) };
+        self
+    }
+
+    /// Loads the bits in `range` as an integer in big-endian order, which is
+    /// the same as [`load_be`](SmallBitVec::load_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([0b10110011, 0b01010101]);
+    /// assert_eq!(vec.load::<u8>(4..12), 0b00110101);
+    /// assert_eq!(vec.load::<i8>(0..4), -5);
+    /// ```
+    #[inline]
+    pub fn load<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        self.load_be(range)
+    }
+
+    /// Loads the bits in `range` as an integer, with the first bit being the
+    /// most significant. See [`BitVec::load_be`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([0b10110011, 0b01010101]);
+    /// assert_eq!(vec.load_be::<u16>(2..14), 0b110011_010101);
+    /// ```
+    pub fn load_be<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::load_be(self.buf.as_slice(), range) }
+    }
+
+    /// Loads the bits in `range` as an integer in little-endian order. See
+    /// [`BitVec::load_le`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([0b10110011, 0b01010101]);
+    /// assert_eq!(vec.load_le::<u16>(0..12), 0b0101_10110011);
+    /// ```
+    pub fn load_le<T: Integer>(&self, range: impl RangeBounds<usize>) -> T {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::load_le(self.buf.as_slice(), range) }
+    }
+
+    /// Stores the lowest bits of `value` into `range` in big-endian order, which
+    /// is the same as [`store_be`](SmallBitVec::store_be).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([0b00000000, 0b00000000]);
+    /// vec.store(4..12, 0xffu8);
+    /// assert_eq!(vec, bitvec![0b00001111, 0b11110000]);
+    /// ```
+    #[inline]
+    pub fn store<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        self.store_be(range, value);
+    }
+
+    /// Stores the lowest bits of `value` into `range`, with the first bit being
+    /// the most significant. See [`BitVec::store_be`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([0b00000000, 0b00000000]);
+    /// vec.store_be(2..14, -1i16);
+    /// assert_eq!(vec, bitvec![0b00111111, 0b11111100]);
+    /// ```
+    pub fn store_be<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::store_be(self.buf.as_mut_slice(), range, value) };
+    }
+
+    /// Stores the lowest bits of `value` into `range` in little-endian order.
+    /// See [`BitVec::store_le`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, or if it spans more bits than `T`
+    /// holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let mut vec = SmallBitVec::<1>::from([0b00000000, 0b00000000]);
+    /// vec.store_le(.., 0x1234u16);
+    /// assert_eq!(vec, bitvec![0x34, 0x12]);
+    /// ```
+    pub fn store_le<T: Integer>(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let range = field::field_range::<T>(range, self.len);
+        unsafe { field::store_le(self.buf.as_mut_slice(), range, value) };
+    }
+
+    /// Returns the index of the first occurrence of `pattern` in the vector, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, false, true, true, false, true, true]);
+    /// assert_eq!(vec.find(&bitvec![true, true]), Some(2));
+    /// assert_eq!(vec.find(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn find<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { search::find(self.buf.as_slice(), self.len, pattern) }
+    }
+
+    /// Returns the index of the last occurrence of `pattern` in the vector, or
+    /// `None` if it does not occur.
+    ///
+    /// An empty pattern matches at the end of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, false, true, true, false, true, true]);
+    /// assert_eq!(vec.rfind(&bitvec![true, true]), Some(5));
+    /// assert_eq!(vec.rfind(&bitvec![false, false]), None);
+    /// ```
+    #[inline]
+    pub fn rfind<B: Allocator>(&self, pattern: &BitVec<B>) -> Option<usize> {
+        unsafe { search::rfind(self.buf.as_slice(), self.len, pattern) }
+    }
+
+    /// Returns an iterator over the indices of the non-overlapping occurrences
+    /// of `pattern` in the vector, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, true, false, true, true]);
+    /// let matches: Vec<usize> = vec.find_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 4]);
+    /// ```
+    #[inline]
+    pub fn find_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(self.buf.as_slice(), self.len, pattern, false) }
+    }
+
+    /// Returns an iterator over the indices of all occurrences of `pattern` in
+    /// the vector, including overlapping ones, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, true, false, true, true]);
+    /// let matches: Vec<usize> = vec.find_overlapping_iter(&bitvec![true, true]).collect();
+    /// assert_eq!(matches, [0, 1, 4]);
+    /// ```
+    #[inline]
+    pub fn find_overlapping_iter<B: Allocator>(&self, pattern: &BitVec<B>) -> Matches<'_> {
+        unsafe { Matches::new(self.buf.as_slice(), self.len, pattern, true) }
+    }
+
+    /// Returns `true` if `pattern` occurs anywhere in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, false, true, true]);
+    /// assert!(vec.contains_pattern(&bitvec![false, true]));
+    /// assert!(!vec.contains_pattern(&bitvec![false, false]));
+    /// ```
+    #[inline]
+    pub fn contains_pattern<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        self.find(pattern).is_some()
+    }
+
+    /// Returns `true` if the vector begins with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, false, true, true]);
+    /// assert!(vec.starts_with(&bitvec![true, false]));
+    /// assert!(!vec.starts_with(&bitvec![true, true]));
+    /// ```
+    #[inline]
+    pub fn starts_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { search::starts_with(self.buf.as_slice(), self.len, pattern) }
+    }
+
+    /// Returns `true` if the vector ends with `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{SmallBitVec, bitvec};
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, false, true, true]);
+    /// assert!(vec.ends_with(&bitvec![true, true]));
+    /// assert!(!vec.ends_with(&bitvec![false, true]));
+    /// ```
+    #[inline]
+    pub fn ends_with<B: Allocator>(&self, pattern: &BitVec<B>) -> bool {
+        unsafe { search::ends_with(self.buf.as_slice(), self.len, pattern) }
+    }
+
+    /// Returns an iterator over the maximal runs of equal bits in the vector,
+    /// yielding `(bit, start, len)` for each run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::SmallBitVec;
+    ///
+    /// let vec = SmallBitVec::<1>::from([true, true, false, false, false, true]);
+    /// let runs: Vec<_> = vec.iter_runs().collect();
+    /// assert_eq!(runs, [(true, 0, 2), (false, 2, 3), (true, 5, 1)]);
+    /// ```
+    #[inline]
+    pub fn iter_runs(&self) -> Runs<'_> {
+        Runs::new(self.buf.as_slice(), 0..self.len)
+    }
+}
+
+impl<const N: usize> Default for SmallBitVec<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Index<usize> for SmallBitVec<N> {
+    type Output = Bit;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            None => panic!("index out of bounds"),
+            Some(false) => &false,
+            Some(true) => &true,
+        }
+    }
+}
+
+impl<const N: usize> Clone for SmallBitVec<N> {
+    fn clone(&self) -> Self {
+        let len = self.len;
+        let buf = match &self.buf {
+            Buf::Inline(buf) => Buf::Inline(*buf),
+            Buf::Heap(buf) => {
+                let buf_used = self.buf_used();
+                let mut heap = Vec::with_capacity(buf_used);
+                heap.extend_from_slice(unsafe { buf.get_unchecked(..buf_used) });
+                Buf::Heap(heap)
+            }
+        };
+        Self { len, buf }
+    }
+}
+
+impl<const N: usize> fmt::Debug for SmallBitVec<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize> Extend<Bit> for SmallBitVec<N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Bit>,
+    {
+        let iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        self.reserve(additional);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, const N: usize> Extend<&'a Bit> for SmallBitVec<N> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a Bit>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<const N: usize> FromIterator<Bit> for SmallBitVec<N> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Bit>,
+    {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, const N: usize> FromIterator<&'a Bit> for SmallBitVec<N> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Bit>,
+    {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl<const N: usize> Hash for SmallBitVec<N> {
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        unsafe { hash_bits(self.len, self.buf.as_slice(), state) };
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<SmallBitVec<M>> for SmallBitVec<N> {
+    #[inline]
+    fn eq(&self, other: &SmallBitVec<M>) -> bool {
+        self.len == other.len
+            && unsafe { eq_bits(self.len, self.buf.as_slice(), other.buf.as_slice()) }
+    }
+}
+
+impl<const N: usize> PartialEq<BitVec> for SmallBitVec<N> {
+    #[inline]
+    fn eq(&self, other: &BitVec) -> bool {
+        self.len == other.len && unsafe { eq_bits(self.len, self.buf.as_slice(), &other.buf) }
+    }
+}
+
+impl<const N: usize> PartialEq<SmallBitVec<N>> for BitVec {
+    #[inline]
+    fn eq(&self, other: &SmallBitVec<N>) -> bool {
+        other == self
+    }
+}
+
+impl<const N: usize> Eq for SmallBitVec<N> {}
+
+impl<const N: usize> From<&[Byte]> for SmallBitVec<N> {
+    fn from(value: &[Byte]) -> Self {
+        let len = value
+            .len()
+            .checked_mul(Byte::BITS as usize)
+            .expect("capacity overflow");
+        let buf_len = value.len().div_ceil(Word::BYTES);
+        let words = value
+            .chunks(Word::BYTES)
+            .map(|chunk| unsafe { Word::from_byte_slice(chunk) });
+        let buf = Buf::from_words(words, buf_len);
+        Self { len, buf }
+    }
+}
+
+impl<const N: usize, const M: usize> From<[Byte; M]> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: [Byte; M]) -> Self {
+        Self::from(&value[..])
+    }
+}
+
+impl<const N: usize> From<Box<[Byte]>> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: Box<[Byte]>) -> Self {
+        Self::from(&value[..])
+    }
+}
+
+impl<const N: usize> From<alloc::vec::Vec<Byte>> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: alloc::vec::Vec<Byte>) -> Self {
+        Self::from(&value[..])
+    }
+}
+
+impl<const N: usize> From<&[Bit]> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: &[Bit]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl<const N: usize, const M: usize> From<[Bit; M]> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: [Bit; M]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<Box<[Bit]>> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: Box<[Bit]>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<alloc::vec::Vec<Bit>> for SmallBitVec<N> {
+    #[inline]
+    fn from(value: alloc::vec::Vec<Bit>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<&SmallBitVec<N>> for Box<[Bit]> {
+    #[inline]
+    fn from(value: &SmallBitVec<N>) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl<const N: usize> From<SmallBitVec<N>> for Box<[Bit]> {
+    #[inline]
+    fn from(value: SmallBitVec<N>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<&SmallBitVec<N>> for alloc::vec::Vec<Bit> {
+    #[inline]
+    fn from(value: &SmallBitVec<N>) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl<const N: usize> From<SmallBitVec<N>> for alloc::vec::Vec<Bit> {
+    #[inline]
+    fn from(value: SmallBitVec<N>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<BitVec> for SmallBitVec<N> {
+    fn from(mut value: BitVec) -> Self {
+        let len = value.len;
+        let buf_len = value.buf_used();
+        let buf = if buf_len <= N {
+            Buf::from_words(value.buf.into_iter(), buf_len)
+        } else {
+            value.buf.truncate(buf_len);
+            Buf::Heap(value.buf)
+        };
+        Self { len, buf }
+    }
+}
+
+impl<const N: usize> From<SmallBitVec<N>> for BitVec {
+    fn from(value: SmallBitVec<N>) -> Self {
+        let len = value.len;
+        let buf = match value.buf {
+            Buf::Inline(buf) => {
+                let buf_used = len.div_ceil(Word::BITS);
+                let mut heap = Vec::with_capacity(buf_used);
+                heap.extend_from_slice(unsafe { buf.get_unchecked(..buf_used) });
+                heap
+            }
+            Buf::Heap(buf) => buf,
+        };
+        Self { len, buf }
+    }
+}
+
+impl<const N: usize> IntoIterator for SmallBitVec<N> {
+    type Item = Bit;
+    type IntoIter = IntoIter<N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let cursor = Cursor::new(0..self.len);
+        let vec = self;
+        IntoIter { vec, cursor }
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a SmallBitVec<N> {
+    type Item = Bit;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the bits of a [`SmallBitVec`].
+#[derive(Debug, Clone)]
+pub struct IntoIter<const N: usize> {
+    vec: SmallBitVec<N>,
+    cursor: Cursor,
+}
+
+impl<const N: usize> Iterator for IntoIter<N> {
+    type Item = Bit;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe { self.cursor.next(self.vec.buf.as_slice()) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.cursor.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.cursor.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth(self.vec.buf.as_slice(), n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.fold(self.vec.buf.as_slice(), init, f) }
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for IntoIter<N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        unsafe { self.cursor.next_back(self.vec.buf.as_slice()) }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        unsafe { self.cursor.nth_back(self.vec.buf.as_slice(), n) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let remaining = self.cursor.advance_back_by(n);
+        NonZero::new(remaining).map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        unsafe { self.cursor.rfold(self.vec.buf.as_slice(), init, f) }
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for IntoIter<N> {}
+impl<const N: usize> FusedIterator for IntoIter<N> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::bitvec;
+    use std::hash::DefaultHasher;
+
+    #[test]
+    fn test_new() {
+        let vec = SmallBitVec::<2>::new();
+        assert_eq!(vec.len, 0);
+        assert!(!vec.spilled());
+        assert_eq!(vec.capacity(), Word::BITS * 2);
+
+        let vec = SmallBitVec::<0>::new();
+        assert_eq!(vec.capacity(), 0);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let vec = SmallBitVec::<2>::with_capacity(Word::BITS * 2);
+        assert!(!vec.spilled());
+
+        let vec = SmallBitVec::<2>::with_capacity(Word::BITS * 2 + 1);
+        assert!(vec.spilled());
+        assert!(vec.capacity() >= Word::BITS * 3);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut vec = SmallBitVec::<1>::new();
+        let mut expected = BitVec::new();
+
+        for index in 0..Word::BITS {
+            vec.push(index % 3 == 0);
+            expected.push(index % 3 == 0);
+        }
+        assert!(!vec.spilled());
+        assert_eq!(vec, expected);
+
+        vec.push(true);
+        expected.push(true);
+        assert!(vec.spilled());
+        assert_eq!(vec, expected);
+
+        while vec.pop().is_some() {
+            expected.pop();
+            assert_eq!(vec, expected);
+        }
+        assert!(vec.spilled());
+    }
+
+    #[test]
+    fn test_push_zero_inline() {
+        let mut vec = SmallBitVec::<0>::new();
+        vec.push(true).push(false);
+        assert!(vec.spilled());
+        assert_eq!(vec, bitvec![true, false]);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+        let unchanged = vec.clone();
+
+        vec.reserve(Word::BITS - 4);
+        assert!(!vec.spilled());
+
+        vec.reserve(Word::BITS);
+        assert!(vec.spilled());
+        assert!(vec.capacity() >= Word::BITS + 4);
+        assert_eq!(vec, unchanged);
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+        let unchanged = vec.clone();
+
+        assert!(vec.try_reserve(Word::BITS - 4).is_ok());
+        assert!(!vec.spilled());
+
+        assert!(vec.try_reserve(Word::BITS).is_ok());
+        assert!(vec.spilled());
+        assert!(vec.capacity() >= Word::BITS + 4);
+
+        let result = vec.try_reserve(usize::MAX);
+        assert_eq!(result.unwrap_err(), TryReserveError::CapacityOverflow);
+
+        let result = vec.try_reserve(usize::MAX - vec.len);
+        assert_eq!(result.unwrap_err(), TryReserveError::AllocError);
+        assert_eq!(vec, unchanged);
+
+        let result = SmallBitVec::<1>::try_with_capacity(usize::MAX);
+        assert_eq!(result.unwrap_err(), TryReserveError::AllocError);
+    }
+
+    #[test]
+    fn test_try_push_extend() {
+        let mut vec = SmallBitVec::<1>::new();
+        vec.try_extend([true; Word::BITS]).unwrap();
+        assert!(!vec.spilled());
+
+        vec.try_push(false).unwrap();
+        assert!(vec.spilled());
+        assert_eq!(vec.len, Word::BITS + 1);
+        assert_eq!(vec.get(Word::BITS), Some(false));
+    }
+
+    #[test]
+    fn test_shrink_to() {
+        let mut vec = SmallBitVec::<1>::from([true; Word::BITS + 1]);
+        assert!(vec.spilled());
+
+        vec.reserve(Word::BITS * 4);
+        vec.shrink_to(Word::BITS * 3);
+        assert!(vec.spilled());
+        assert!(vec.capacity() >= Word::BITS * 3);
+
+        vec.shrink_to_fit();
+        assert!(vec.spilled());
+        assert_eq!(vec.capacity(), Word::BITS * 2);
+
+        vec.pop();
+        vec.shrink_to_fit();
+        assert!(!vec.spilled());
+        assert_eq!(vec, bitvec![true; Word::BITS]);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut vec = SmallBitVec::<1>::from([true, true, false, false]);
+
+        assert_eq!(vec.get(0), Some(true));
+        assert_eq!(vec.get(3), Some(false));
+        assert_eq!(vec.get(4), None);
+
+        assert!(vec.set(3, true).is_some());
+        assert!(vec.set(4, true).is_none());
+        assert!(vec[3]);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let vec = SmallBitVec::<1>::from([0b11110000]);
+        assert!(!vec.spilled());
+        assert_eq!(vec, bitvec![0b11110000]);
+
+        let vec = SmallBitVec::<1>::from([0b00001111; Word::BYTES + 1]);
+        assert!(vec.spilled());
+        assert_eq!(vec, BitVec::from([0b00001111; Word::BYTES + 1]));
+    }
+
+    #[test]
+    fn test_bitvec_conversion() {
+        let mut vec = bitvec![true, true, false, false];
+        vec.push_unused_word();
+
+        let small = SmallBitVec::<1>::from(vec.clone());
+        assert!(!small.spilled());
+        assert_eq!(small, vec);
+        assert_eq!(BitVec::from(small), vec);
+
+        let small = SmallBitVec::<0>::from(vec.clone());
+        assert!(small.spilled());
+        assert_eq!(small, vec);
+        assert_eq!(BitVec::from(small), vec);
+    }
+
+    #[test]
+    fn test_vec_conversion() {
+        let bits = alloc::vec![true, false, true];
+        let vec = SmallBitVec::<1>::from(bits.clone());
+        assert_eq!(vec, bitvec![true, false, true]);
+        assert_eq!(alloc::vec::Vec::from(&vec), bits);
+        assert_eq!(
+            Box::<[Bit]>::from(vec.clone()),
+            bits.clone().into_boxed_slice()
+        );
+        assert_eq!(alloc::vec::Vec::from(vec), bits);
+
+        let bytes = alloc::vec![0b11110000; Word::BYTES + 1];
+        let vec = SmallBitVec::<1>::from(bytes.clone());
+        assert!(vec.spilled());
+        assert_eq!(vec, BitVec::from(bytes.clone()));
+        assert_eq!(SmallBitVec::<1>::from(bytes.into_boxed_slice()), vec);
+        assert_eq!(vec.as_bitslice(), BitVec::from(vec.clone()));
+    }
+
+    #[test]
+    fn test_iter() {
+        let vec = SmallBitVec::<1>::from([true, true, false, false]);
+
+        let mut iter = vec.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_matches_bitvec() {
+        let len = Word::BITS * 2 + 5;
+        let mut vec: BitVec = (0..len).map(|index| index % 3 == 0).collect();
+        vec.push(true);
+        vec.pop();
+        let mut small = SmallBitVec::<3>::from(vec.clone());
+        assert!(!small.spilled());
+
+        vec.fill_range(2..Word::BITS + 3, true);
+        small.fill_range(2..Word::BITS + 3, true);
+        assert!(small.iter().eq(vec.iter()));
+        vec.toggle_range(Word::BITS..);
+        small.toggle_range(Word::BITS..);
+        assert!(small.iter().eq(vec.iter()));
+        vec.copy_within(1..Word::BITS + 1, 4);
+        small.copy_within(1..Word::BITS + 1, 4);
+        assert!(small.iter().eq(vec.iter()));
+        vec.swap(0, len - 1);
+        small.swap(0, len - 1);
+        assert!(small.iter().eq(vec.iter()));
+        vec.reverse();
+        small.reverse();
+        assert!(small.iter().eq(vec.iter()));
+        vec.reverse_range(3..len - 2);
+        small.reverse_range(3..len - 2);
+        assert!(small.iter().eq(vec.iter()));
+
+        vec.store_le(5..21, 0x1234u16);
+        small.store_le(5..21, 0x1234u16);
+        vec.store(Word::BITS - 3..Word::BITS + 9, -7i16);
+        small.store(Word::BITS - 3..Word::BITS + 9, -7i16);
+        assert!(small.iter().eq(vec.iter()));
+        assert_eq!(small.load_le::<u16>(5..21), 0x1234);
+        assert_eq!(small.load::<i16>(Word::BITS - 3..Word::BITS + 9), -7);
+        assert_eq!(small.as_bitslice(), vec);
+
+        for pattern in [bitvec![], bitvec![true, true], bitvec![false, true, false]] {
+            assert_eq!(small.find(&pattern), vec.find(&pattern));
+            assert_eq!(small.rfind(&pattern), vec.rfind(&pattern));
+            assert!(small.find_iter(&pattern).eq(vec.find_iter(&pattern)));
+            assert!(
+                small
+                    .find_overlapping_iter(&pattern)
+                    .eq(vec.find_overlapping_iter(&pattern))
+            );
+            assert_eq!(small.starts_with(&pattern), vec.starts_with(&pattern));
+            assert_eq!(small.ends_with(&pattern), vec.ends_with(&pattern));
+        }
+
+        assert!(small.set_range(len..len + 1, true).is_none());
+        assert_eq!(small.fill(false).count_ones(), 0);
+
+        small.extend([true; Word::BITS]);
+        assert!(small.spilled());
+        assert!(
+            small
+                .iter_runs()
+                .eq([(false, 0, len), (true, len, Word::BITS)])
+        );
+    }
+
+    #[test]
+    fn test_hash_eq() {
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let lhs = SmallBitVec::<1>::from([true; Word::BITS]);
+        let mut rhs = SmallBitVec::<0>::from([true; Word::BITS]);
+        rhs.push(false).pop();
+
+        assert_eq!(lhs, rhs);
+        assert_eq!(hash(&lhs), hash(&rhs));
+        assert_eq!(hash(&lhs), hash(&bitvec![true; Word::BITS]));
+    }
+}
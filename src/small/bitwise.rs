@@ -0,0 +1,140 @@
+use super::{Buf, SmallBitVec};
+use crate::primitive::Word;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+impl<const N: usize> SmallBitVec<N> {
+    fn bitwise_operation<F>(&self, rhs: &Self, op: F) -> Self
+    where
+        F: FnMut((&Word, &Word)) -> Word,
+    {
+        let len = self.len.min(rhs.len);
+        let buf_len = len.div_ceil(Word::BITS);
+        let words = self.buf.as_slice().iter().zip(rhs.buf.as_slice()).map(op);
+        let buf = Buf::from_words(words, buf_len);
+        Self { len, buf }
+    }
+}
+
+impl<const N: usize> BitAnd for &SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `&` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitand(self, rhs: &SmallBitVec<N>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left & *right)
+    }
+}
+
+impl<const N: usize> BitAnd for SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `&` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitand(self, rhs: SmallBitVec<N>) -> Self::Output {
+        &self & &rhs
+    }
+}
+
+impl<const N: usize> BitOr for &SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `|` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitor(self, rhs: &SmallBitVec<N>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left | *right)
+    }
+}
+
+impl<const N: usize> BitOr for SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `|` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitor(self, rhs: SmallBitVec<N>) -> Self::Output {
+        &self | &rhs
+    }
+}
+
+impl<const N: usize> BitXor for &SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `^` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitxor(self, rhs: &SmallBitVec<N>) -> Self::Output {
+        self.bitwise_operation(rhs, |(left, right)| *left ^ *right)
+    }
+}
+
+impl<const N: usize> BitXor for SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    /// Performs the `^` operation, returning a new `SmallBitVec` with the length
+    /// of the shorter input.
+    #[inline]
+    fn bitxor(self, rhs: SmallBitVec<N>) -> Self::Output {
+        &self ^ &rhs
+    }
+}
+
+impl<const N: usize> Not for SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    #[inline]
+    fn not(mut self) -> Self::Output {
+        let buf_len = self.buf_used();
+        self.buf
+            .as_mut_slice()
+            .iter_mut()
+            .take(buf_len)
+            .for_each(|word| *word = !*word);
+        self
+    }
+}
+
+impl<const N: usize> Not for &SmallBitVec<N> {
+    type Output = SmallBitVec<N>;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        let len = self.len;
+        let buf_len = self.buf_used();
+        let words = self.buf.as_slice().iter().map(|word| !*word);
+        let buf = Buf::from_words(words, buf_len);
+        SmallBitVec { len, buf }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SmallBitVec;
+    use crate::primitive::Word;
+
+    const LONG: usize = Word::BITS * 2 + 1;
+    const SHORT: usize = Word::BITS + 1;
+
+    #[test]
+    fn test_bitwise() {
+        let lhs = SmallBitVec::<2>::from([true; LONG]);
+        let rhs = SmallBitVec::<2>::from([false; SHORT]);
+        assert!(lhs.spilled());
+        assert!(!rhs.spilled());
+
+        let result = &lhs & &rhs;
+        assert!(!result.spilled());
+        assert_eq!(result, SmallBitVec::<2>::from([false; SHORT]));
+
+        assert_eq!(&lhs | &rhs, SmallBitVec::<2>::from([true; SHORT]));
+        assert_eq!(&lhs ^ &rhs, SmallBitVec::<2>::from([true; SHORT]));
+        assert_eq!(
+            lhs.clone() & rhs.clone(),
+            SmallBitVec::<2>::from([false; SHORT])
+        );
+        assert_eq!(!&lhs, SmallBitVec::<2>::from([false; LONG]));
+        assert_eq!(!rhs, SmallBitVec::<2>::from([true; SHORT]));
+    }
+}
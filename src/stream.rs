@@ -0,0 +1,329 @@
+use crate::primitive::{Bit, Byte, Word};
+use crate::slice::{load_bits, store_bits};
+use crate::{BitSlice, BitVec, UnderrunError};
+use allocator_api2::alloc::{Allocator, Global};
+use core::ops::Range;
+
+mod codes;
+
+/// Appends fields of arbitrary bit widths to a [`BitVec`].
+///
+/// # Examples
+///
+/// ```
+/// use bitvek::{BitReader, BitVec, BitWriter};
+///
+/// let mut vec = BitVec::new();
+/// let mut writer = BitWriter::new(&mut vec);
+/// writer.write_bits(0b101, 3).write_bits(1000, 11).write_bool(true);
+/// assert_eq!(writer.position(), 15);
+///
+/// let mut reader = BitReader::from(&vec);
+/// assert_eq!(reader.read_bits(3), Ok(0b101));
+/// assert_eq!(reader.read_bits(11), Ok(1000));
+/// assert_eq!(reader.read_bool(), Ok(true));
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<'a, A: Allocator = Global> {
+    vec: &'a mut BitVec<A>,
+}
+
+impl<'a, A: Allocator> BitWriter<'a, A> {
+    /// Creates a writer appending to the end of `vec`.
+    #[inline]
+    pub fn new(vec: &'a mut BitVec<A>) -> Self {
+        Self { vec }
+    }
+
+    /// Returns the number of bits in the underlying vector, which is the index
+    /// of the next bit to be written.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.vec.len
+    }
+
+    /// Appends the lowest `count` bits of `value`, most significant bit first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds 64, or if the new length exceeds `usize::MAX`
+    /// bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_bits(0b1110, 3);
+    /// assert_eq!(vec, bitvec![true, true, false]);
+    /// ```
+    pub fn write_bits(&mut self, value: u64, count: usize) -> &mut Self {
+        assert!(count <= 64, "count must be at most 64");
+        let start = self.vec.len;
+        let end = start.checked_add(count).expect("capacity overflow");
+        let buf_len = end.div_ceil(Word::BITS);
+        if self.vec.buf.len() < buf_len {
+            self.vec.buf.resize(buf_len, Word::CLEAR);
+        }
+        unsafe { store_bits(&mut self.vec.buf, start..end, value as u128) };
+        self.vec.len = end;
+        self
+    }
+
+    /// Appends a single bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new length exceeds `usize::MAX` bits.
+    #[inline]
+    pub fn write_bool(&mut self, value: Bit) -> &mut Self {
+        self.vec.push(value);
+        self
+    }
+
+    /// Appends zero bits until the length is a multiple of 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter};
+    ///
+    /// let mut vec = BitVec::new();
+    /// let mut writer = BitWriter::new(&mut vec);
+    /// writer.write_bits(0b101, 3).align_to_byte();
+    /// assert_eq!(writer.position(), 8);
+    /// writer.align_to_byte();
+    /// assert_eq!(writer.position(), 8);
+    /// ```
+    pub fn align_to_byte(&mut self) -> &mut Self {
+        let padding = self.vec.len.next_multiple_of(Byte::BITS as usize) - self.vec.len;
+        self.write_bits(0, padding)
+    }
+}
+
+/// Reads fields of arbitrary bit widths from a [`BitVec`] or a [`BitSlice`],
+/// front to back.
+///
+/// Reads that need more bits than remain fail with an [`UnderrunError`] and
+/// leave the reader unchanged.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    buf: &'a [Word],
+    start: usize,
+    range: Range<usize>,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader over the bits of `slice`.
+    #[inline]
+    pub fn new(slice: BitSlice<'a>) -> Self {
+        let buf = slice.buf();
+        let range = slice.range();
+        let start = range.start;
+        Self { buf, start, range }
+    }
+
+    /// Returns the number of bits read or skipped so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.range.start - self.start
+    }
+
+    /// Returns the number of bits left to read.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns `true` if there are no bits left to read.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Reads the next `count` bits as an unsigned integer, most significant bit
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnderrunError`] if fewer than `count` bits remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitReader, bitvec};
+    ///
+    /// let vec = bitvec![true, true, false, true];
+    /// let mut reader = BitReader::from(&vec);
+    /// assert_eq!(reader.read_bits(3), Ok(0b110));
+    /// assert!(reader.read_bits(2).is_err());
+    /// assert_eq!(reader.read_bits(1), Ok(0b1));
+    /// ```
+    pub fn read_bits(&mut self, count: usize) -> Result<u64, UnderrunError> {
+        assert!(count <= 64, "count must be at most 64");
+        let range = self.advance(count)?;
+        Ok(unsafe { load_bits(self.buf, range) } as u64)
+    }
+
+    /// Reads the next bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnderrunError`] if no bits remain.
+    #[inline]
+    pub fn read_bool(&mut self) -> Result<Bit, UnderrunError> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+
+    /// Skips the next `count` bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnderrunError`] if fewer than `count` bits remain.
+    #[inline]
+    pub fn skip(&mut self, count: usize) -> Result<(), UnderrunError> {
+        self.advance(count).map(|_| ())
+    }
+
+    /// Skips bits until the position is a multiple of 8.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnderrunError`] if the reader ends before the next byte
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitReader, bitvec};
+    ///
+    /// let vec = bitvec![0b10100000, 0b11000000];
+    /// let mut reader = BitReader::from(&vec);
+    /// reader.read_bits(3)?;
+    /// reader.align_to_byte()?;
+    /// assert_eq!(reader.read_bits(2), Ok(0b11));
+    /// # Ok::<(), bitvek::UnderrunError>(())
+    /// ```
+    pub fn align_to_byte(&mut self) -> Result<(), UnderrunError> {
+        let position = self.position();
+        self.skip(position.next_multiple_of(Byte::BITS as usize) - position)
+    }
+
+    fn advance(&mut self, count: usize) -> Result<Range<usize>, UnderrunError> {
+        let available = self.remaining();
+        if count > available {
+            return Err(UnderrunError {
+                requested: count,
+                available,
+            });
+        }
+        let start = self.range.start;
+        self.range.start += count;
+        Ok(start..start + count)
+    }
+}
+
+impl<'a> From<BitSlice<'a>> for BitReader<'a> {
+    #[inline]
+    fn from(value: BitSlice<'a>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'a, A: Allocator> From<&'a BitVec<A>> for BitReader<'a> {
+    #[inline]
+    fn from(value: &'a BitVec<A>) -> Self {
+        Self::new(value.as_bitslice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec;
+
+    #[test]
+    fn test_round_trip() {
+        let fields: [(u64, usize); 7] = [
+            (0b101, 3),
+            (0x5a5, 11),
+            (1, 1),
+            (0, 0),
+            (u64::MAX, 64),
+            (0x1234_5678_9abc, 48),
+            (0b11, 2),
+        ];
+        let mut vec = bitvec![true];
+        vec.push_unused_word();
+        let mut writer = BitWriter::new(&mut vec);
+        for (value, count) in fields {
+            writer.write_bits(value, count);
+        }
+        writer.write_bits(u64::MAX, 5).write_bool(false);
+        assert_eq!(writer.position(), 1 + 129 + 6);
+
+        let mut reader = BitReader::from(&vec);
+        assert_eq!(reader.read_bool(), Ok(true));
+        for (value, count) in fields {
+            assert_eq!(reader.read_bits(count), Ok(value));
+        }
+        assert_eq!(reader.read_bits(5), Ok(0b11111));
+        assert_eq!(reader.read_bool(), Ok(false));
+        assert!(reader.is_empty());
+        assert_eq!(reader.position(), vec.len());
+    }
+
+    #[test]
+    fn test_align_and_skip() {
+        let mut vec = BitVec::new();
+        let mut writer = BitWriter::new(&mut vec);
+        writer
+            .write_bits(0b111, 3)
+            .align_to_byte()
+            .write_bits(0xab, 8);
+        assert_eq!(vec, bitvec![0b11100000, 0xab]);
+
+        let mut reader = BitReader::new(BitSlice::new(&vec.buf, 1..16));
+        assert_eq!(reader.remaining(), 15);
+        reader.skip(2).unwrap();
+        reader.align_to_byte().unwrap();
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.read_bits(4), Ok(0b0101));
+        assert_eq!(
+            reader.align_to_byte(),
+            Err(UnderrunError {
+                requested: 4,
+                available: 3
+            })
+        );
+        assert_eq!(
+            reader.skip(4),
+            Err(UnderrunError {
+                requested: 4,
+                available: 3
+            })
+        );
+        assert_eq!(reader.position(), 12);
+        assert_eq!(reader.read_bits(3), Ok(0b011));
+        assert_eq!(
+            reader.read_bool(),
+            Err(UnderrunError {
+                requested: 1,
+                available: 0
+            })
+        );
+        assert_eq!(reader.read_bits(0), Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "count must be at most 64")]
+    fn test_read_too_wide() {
+        let vec = bitvec![true; 100];
+        let _ = BitReader::from(&vec).read_bits(65);
+    }
+}
@@ -0,0 +1,706 @@
+use super::{BitReader, BitWriter};
+use crate::DecodeError;
+use allocator_api2::alloc::Allocator;
+
+/// The Fibonacci numbers F(2) = 1, F(3) = 2, ... up to the largest one that
+/// fits in a `u64`, which are the weights of the bits of a Fibonacci code.
+const FIBONACCI: [u64; 92] = {
+    let mut table = [0; 92];
+    (table[0], table[1]) = (1, 2);
+    let mut index = 2;
+    while index < table.len() {
+        table[index] = table[index - 1] + table[index - 2];
+        index += 1;
+    }
+    table
+};
+
+impl<A: Allocator> BitWriter<'_, A> {
+    /// Appends `value` in unary, as `value` one bits followed by a zero bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_unary(3);
+    /// assert_eq!(vec, bitvec![true, true, true, false]);
+    /// ```
+    pub fn write_unary(&mut self, value: u64) -> &mut Self {
+        let mut value = value;
+        while value >= 64 {
+            self.write_bits(u64::MAX, 64);
+            value -= 64;
+        }
+        self.write_bits(u64::MAX, value as usize).write_bool(false)
+    }
+
+    /// Appends `value` in the Elias gamma code: the bit length of `value` minus
+    /// one in zero bits, followed by `value` itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_elias_gamma(5);
+    /// assert_eq!(vec, bitvec![false, false, true, false, true]);
+    /// ```
+    pub fn write_elias_gamma(&mut self, value: u64) -> &mut Self {
+        assert!(value != 0, "value must be positive");
+        let len = bit_len(value);
+        self.write_bits(0, len - 1).write_bits(value, len)
+    }
+
+    /// Appends `value` in the Elias delta code: the bit length of `value` in the
+    /// Elias gamma code, followed by `value` without its leading one bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_elias_delta(5);
+    /// assert_eq!(vec, bitvec![false, true, true, false, true]);
+    /// ```
+    pub fn write_elias_delta(&mut self, value: u64) -> &mut Self {
+        assert!(value != 0, "value must be positive");
+        let len = bit_len(value);
+        self.write_elias_gamma(len as u64)
+            .write_bits(value, len - 1)
+    }
+
+    /// Appends `value` in the Elias omega code: `value`, preceded recursively
+    /// by the bit length of each group minus one, down to a length of one,
+    /// and followed by a zero bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_elias_omega(5);
+    /// assert_eq!(vec, bitvec![true, false, true, false, true, false]);
+    /// ```
+    pub fn write_elias_omega(&mut self, value: u64) -> &mut Self {
+        assert!(value != 0, "value must be positive");
+        // At most four groups precede the terminating zero: 64, 6, 3 and 2 bits
+        // wide in the worst case.
+        let mut groups = [(0, 0); 4];
+        let mut count = 0;
+        let mut value = value;
+        while value > 1 {
+            let len = bit_len(value);
+            groups[count] = (value, len);
+            count += 1;
+            value = len as u64 - 1;
+        }
+        for &(value, len) in groups[..count].iter().rev() {
+            self.write_bits(value, len);
+        }
+        self.write_bool(false)
+    }
+
+    /// Appends `value` in the Fibonacci code: the bits of its Zeckendorf
+    /// representation, least significant first, followed by a one bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_fibonacci(4);
+    /// assert_eq!(vec, bitvec![true, false, true, true]);
+    /// ```
+    pub fn write_fibonacci(&mut self, value: u64) -> &mut Self {
+        assert!(value != 0, "value must be positive");
+        let len = FIBONACCI.partition_point(|&fib| fib <= value);
+        let mut bits = [false; FIBONACCI.len()];
+        let mut rest = value;
+        for index in (0..len).rev() {
+            if FIBONACCI[index] <= rest {
+                bits[index] = true;
+                rest -= FIBONACCI[index];
+            }
+        }
+        for &bit in &bits[..len] {
+            self.write_bool(bit);
+        }
+        self.write_bool(true)
+    }
+
+    /// Appends `value` in the Golomb code with divisor `m`: the quotient in
+    /// unary, followed by the remainder in truncated binary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_golomb(10, 3);
+    /// assert_eq!(vec, bitvec![true, true, true, false, true, false]);
+    /// ```
+    pub fn write_golomb(&mut self, value: u64, m: u64) -> &mut Self {
+        assert!(m != 0, "m must be positive");
+        let (len, cutoff) = truncated_binary(m);
+        let remainder = value % m;
+        self.write_unary(value / m);
+        if remainder < cutoff {
+            self.write_bits(remainder, len - 1)
+        } else {
+            self.write_bits(remainder + cutoff, len)
+        }
+    }
+
+    /// Appends `value` in the Golomb–Rice code with parameter `k`, that is, the
+    /// Golomb code with divisor `2^k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` exceeds 63.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_rice(9, 2);
+    /// assert_eq!(vec, bitvec![true, true, false, false, true]);
+    /// ```
+    pub fn write_rice(&mut self, value: u64, k: usize) -> &mut Self {
+        assert!(k < 64, "k must be less than 64");
+        self.write_unary(value >> k).write_bits(value, k)
+    }
+
+    /// Appends `value` in the Exp-Golomb code of order `k`. Order 0 is the
+    /// `ue(v)` code of H.264.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` exceeds 63.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_exp_golomb(3, 0);
+    /// assert_eq!(vec, bitvec![false, false, true, false, false]);
+    /// ```
+    #[inline]
+    pub fn write_exp_golomb(&mut self, value: u64, k: usize) -> &mut Self {
+        assert!(k < 64, "k must be less than 64");
+        self.write_exp_golomb_wide(value as u128, k)
+    }
+
+    /// Appends `value` in the signed Exp-Golomb code, the `se(v)` code of
+    /// H.264, which maps positive values to odd and other values to even code
+    /// numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitVec, BitWriter, bitvec};
+    ///
+    /// let mut vec = BitVec::new();
+    /// BitWriter::new(&mut vec).write_signed_exp_golomb(-1);
+    /// assert_eq!(vec, bitvec![false, true, true]);
+    /// ```
+    pub fn write_signed_exp_golomb(&mut self, value: i64) -> &mut Self {
+        let magnitude = value.unsigned_abs() as u128;
+        let code = if value > 0 {
+            magnitude * 2 - 1
+        } else {
+            magnitude * 2
+        };
+        self.write_exp_golomb_wide(code, 0)
+    }
+
+    /// Writes a code number of up to 65 bits, as needed by the signed code.
+    fn write_exp_golomb_wide(&mut self, value: u128, k: usize) -> &mut Self {
+        let value = value + (1 << k);
+        let len = (u128::BITS - value.leading_zeros()) as usize;
+        self.write_bits(0, len - k - 1)
+            .write_bits((value >> 64) as u64, len.saturating_sub(64))
+            .write_bits(value as u64, len.min(64))
+    }
+}
+
+impl BitReader<'_> {
+    /// Reads a value in unary, as written by [`BitWriter::write_unary`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends before the terminating zero
+    /// bit.
+    #[inline]
+    pub fn read_unary(&mut self) -> Result<u64, DecodeError> {
+        self.decode(Self::read_unary_raw)
+    }
+
+    /// Reads a value in the Elias gamma code, as written by
+    /// [`BitWriter::write_elias_gamma`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvek::{BitReader, bitvec};
+    ///
+    /// let vec = bitvec![false, false, true, false, true];
+    /// assert_eq!(BitReader::from(&vec).read_elias_gamma(), Ok(5));
+    /// ```
+    pub fn read_elias_gamma(&mut self) -> Result<u64, DecodeError> {
+        self.decode(Self::read_elias_gamma_raw)
+    }
+
+    /// Reads a value in the Elias delta code, as written by
+    /// [`BitWriter::write_elias_delta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    pub fn read_elias_delta(&mut self) -> Result<u64, DecodeError> {
+        self.decode(|reader| {
+            let len = reader.read_elias_gamma_raw()?;
+            if len > 64 {
+                return Err(DecodeError::Overflow);
+            }
+            let rest = reader.read_bits(len as usize - 1)?;
+            Ok(1 << (len - 1) | rest)
+        })
+    }
+
+    /// Reads a value in the Elias omega code, as written by
+    /// [`BitWriter::write_elias_omega`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    pub fn read_elias_omega(&mut self) -> Result<u64, DecodeError> {
+        self.decode(|reader| {
+            let mut value: u64 = 1;
+            while reader.read_bool()? {
+                if value >= 64 {
+                    return Err(DecodeError::Overflow);
+                }
+                value = 1 << value | reader.read_bits(value as usize)?;
+            }
+            Ok(value)
+        })
+    }
+
+    /// Reads a value in the Fibonacci code, as written by
+    /// [`BitWriter::write_fibonacci`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends before the terminating pair
+    /// of one bits, or if the value does not fit in a `u64`.
+    pub fn read_fibonacci(&mut self) -> Result<u64, DecodeError> {
+        self.decode(|reader| {
+            let mut value: u64 = 0;
+            let mut prev = false;
+            for index in 0.. {
+                let bit = reader.read_bool()?;
+                if bit && prev {
+                    break;
+                }
+                if bit {
+                    let fib = FIBONACCI.get(index).ok_or(DecodeError::Overflow)?;
+                    value = value.checked_add(*fib).ok_or(DecodeError::Overflow)?;
+                }
+                prev = bit;
+            }
+            Ok(value)
+        })
+    }
+
+    /// Reads a value in the Golomb code with divisor `m`, as written by
+    /// [`BitWriter::write_golomb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    pub fn read_golomb(&mut self, m: u64) -> Result<u64, DecodeError> {
+        assert!(m != 0, "m must be positive");
+        let (len, cutoff) = truncated_binary(m);
+        self.decode(|reader| {
+            let quotient = reader.read_unary_raw()?;
+            if len == 0 {
+                return Ok(quotient);
+            }
+            let mut remainder = reader.read_bits(len - 1)?;
+            if remainder >= cutoff {
+                remainder = (remainder << 1 | reader.read_bits(1)?) - cutoff;
+            }
+            quotient
+                .checked_mul(m)
+                .and_then(|value| value.checked_add(remainder))
+                .ok_or(DecodeError::Overflow)
+        })
+    }
+
+    /// Reads a value in the Golomb–Rice code with parameter `k`, as written by
+    /// [`BitWriter::write_rice`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` exceeds 63.
+    pub fn read_rice(&mut self, k: usize) -> Result<u64, DecodeError> {
+        assert!(k < 64, "k must be less than 64");
+        self.decode(|reader| {
+            let quotient = reader.read_unary_raw()?;
+            if quotient.leading_zeros() < k as u32 {
+                return Err(DecodeError::Overflow);
+            }
+            Ok(quotient << k | reader.read_bits(k)?)
+        })
+    }
+
+    /// Reads a value in the Exp-Golomb code of order `k`, as written by
+    /// [`BitWriter::write_exp_golomb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` exceeds 63.
+    pub fn read_exp_golomb(&mut self, k: usize) -> Result<u64, DecodeError> {
+        assert!(k < 64, "k must be less than 64");
+        self.decode(|reader| {
+            let value = reader.read_exp_golomb_wide(k)?;
+            u64::try_from(value).map_err(|_| DecodeError::Overflow)
+        })
+    }
+
+    /// Reads a value in the signed Exp-Golomb code, as written by
+    /// [`BitWriter::write_signed_exp_golomb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the reader ends in the middle of the code,
+    /// or if the value does not fit in an `i64`.
+    pub fn read_signed_exp_golomb(&mut self) -> Result<i64, DecodeError> {
+        self.decode(|reader| {
+            let code = reader.read_exp_golomb_wide(0)?;
+            let magnitude = code.div_ceil(2) as i128;
+            let value = if code % 2 == 1 { magnitude } else { -magnitude };
+            i64::try_from(value).map_err(|_| DecodeError::Overflow)
+        })
+    }
+
+    /// Runs `f` on a copy of the reader, committing its progress only if it
+    /// succeeds.
+    fn decode<T, F>(&mut self, f: F) -> Result<T, DecodeError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, DecodeError>,
+    {
+        let mut reader = self.clone();
+        let value = f(&mut reader)?;
+        *self = reader;
+        Ok(value)
+    }
+
+    fn read_unary_raw(&mut self) -> Result<u64, DecodeError> {
+        let mut value: u64 = 0;
+        while self.read_bool()? {
+            value = value.checked_add(1).ok_or(DecodeError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    /// Reads zero bits up to and including the next one bit, returning the
+    /// number of zero bits, which must not exceed `limit`.
+    fn read_leading_zeros(&mut self, limit: usize) -> Result<usize, DecodeError> {
+        let mut zeros = 0;
+        while !self.read_bool()? {
+            zeros += 1;
+            if zeros > limit {
+                return Err(DecodeError::Overflow);
+            }
+        }
+        Ok(zeros)
+    }
+
+    fn read_elias_gamma_raw(&mut self) -> Result<u64, DecodeError> {
+        let zeros = self.read_leading_zeros(63)?;
+        Ok(1 << zeros | self.read_bits(zeros)?)
+    }
+
+    fn read_exp_golomb_wide(&mut self, k: usize) -> Result<u128, DecodeError> {
+        // The bits after the leading one number at most 64.
+        let len = self.read_leading_zeros(64 - k)? + k;
+        let value = 1 << len | self.read_bits(len)? as u128;
+        Ok(value - (1 << k))
+    }
+}
+
+fn bit_len(value: u64) -> usize {
+    (u64::BITS - value.leading_zeros()) as usize
+}
+
+/// Returns the bit length of the longer codewords of the truncated binary code
+/// for `m` symbols, and the number of symbols encoded one bit shorter.
+fn truncated_binary(m: u64) -> (usize, u64) {
+    let len = bit_len(m - 1);
+    let cutoff = ((1u128 << len) - m as u128) as u64;
+    (len, cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BitReader, BitVec, BitWriter, DecodeError, UnderrunError, bitvec};
+    use alloc::vec::Vec;
+
+    fn samples() -> Vec<u64> {
+        let mut values: Vec<u64> = (1..=40).collect();
+        for shift in 0..64 {
+            let power = 1 << shift;
+            values.extend(
+                [power - 1, power, power + 1]
+                    .into_iter()
+                    .filter(|&v| v != 0),
+            );
+        }
+        values.push(u64::MAX);
+        values
+    }
+
+    fn round_trip(
+        values: &[u64],
+        write: impl Fn(&mut BitWriter<'_>, u64),
+        read: impl Fn(&mut BitReader<'_>) -> Result<u64, DecodeError>,
+    ) {
+        let mut vec = BitVec::new();
+        let mut writer = BitWriter::new(&mut vec);
+        for &value in values {
+            write(&mut writer, value);
+        }
+        let mut reader = BitReader::from(&vec);
+        for &value in values {
+            assert_eq!(read(&mut reader), Ok(value));
+        }
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_elias() {
+        let values = samples();
+        round_trip(
+            &values,
+            |w, v| {
+                w.write_elias_gamma(v);
+            },
+            |r| r.read_elias_gamma(),
+        );
+        round_trip(
+            &values,
+            |w, v| {
+                w.write_elias_delta(v);
+            },
+            |r| r.read_elias_delta(),
+        );
+        round_trip(
+            &values,
+            |w, v| {
+                w.write_elias_omega(v);
+            },
+            |r| r.read_elias_omega(),
+        );
+        round_trip(
+            &values,
+            |w, v| {
+                w.write_fibonacci(v);
+            },
+            |r| r.read_fibonacci(),
+        );
+
+        let mut vec = BitVec::new();
+        BitWriter::new(&mut vec)
+            .write_elias_omega(1)
+            .write_elias_omega(16)
+            .write_fibonacci(1)
+            .write_fibonacci(12);
+        let expected = ["0", "10 100 10000 0", "11", "101011"];
+        let expected: BitVec = expected
+            .concat()
+            .bytes()
+            .filter(|&b| b != b' ')
+            .map(|b| b == b'1')
+            .collect();
+        assert_eq!(vec, expected);
+    }
+
+    #[test]
+    fn test_golomb() {
+        let small: Vec<u64> = (0..200).collect();
+        for m in [1, 2, 3, 5, 8, 10, 1000] {
+            round_trip(
+                &small,
+                |w, v| {
+                    w.write_golomb(v, m);
+                },
+                |r| r.read_golomb(m),
+            );
+        }
+        for k in [0, 1, 3, 7] {
+            round_trip(
+                &small,
+                |w, v| {
+                    w.write_rice(v, k);
+                },
+                |r| r.read_rice(k),
+            );
+        }
+        let large = [u64::MAX, u64::MAX - 1, 1 << 63];
+        round_trip(
+            &large,
+            |w, v| {
+                w.write_rice(v, 60);
+            },
+            |r| r.read_rice(60),
+        );
+        round_trip(
+            &large,
+            |w, v| {
+                w.write_golomb(v, u64::MAX);
+            },
+            |r| r.read_golomb(u64::MAX),
+        );
+        round_trip(
+            &[0, 3, 1000],
+            |w, v| {
+                w.write_unary(v);
+            },
+            |r| r.read_unary(),
+        );
+    }
+
+    #[test]
+    fn test_exp_golomb() {
+        let mut values = samples();
+        values.push(0);
+        for k in [0, 1, 5, 63] {
+            round_trip(
+                &values,
+                |w, v| {
+                    w.write_exp_golomb(v, k);
+                },
+                |r| r.read_exp_golomb(k),
+            );
+        }
+
+        let signed = [0, 1, -1, 2, -2, 1000, -1000, i64::MAX, i64::MIN];
+        let mut vec = BitVec::new();
+        let mut writer = BitWriter::new(&mut vec);
+        for value in signed {
+            writer.write_signed_exp_golomb(value);
+        }
+        let mut reader = BitReader::from(&vec);
+        for value in signed {
+            assert_eq!(reader.read_signed_exp_golomb(), Ok(value));
+        }
+
+        let mut vec = BitVec::new();
+        let mut writer = BitWriter::new(&mut vec);
+        for value in 0..4 {
+            writer.write_exp_golomb(value, 0);
+        }
+        assert_eq!(
+            vec,
+            bitvec![
+                true, false, true, false, false, true, true, false, false, true, false, false
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let vec = bitvec![false, false, true, false];
+        let mut reader = BitReader::from(&vec);
+        assert_eq!(
+            reader.read_elias_gamma(),
+            Err(DecodeError::Underrun(UnderrunError {
+                requested: 2,
+                available: 1
+            }))
+        );
+        assert_eq!(reader.position(), 0);
+        assert_eq!(reader.read_unary(), Ok(0));
+        assert_eq!(reader.read_exp_golomb(0), Ok(1));
+        assert!(reader.is_empty());
+
+        let vec = BitVec::from_runs([(false, 64), (true, 1), (false, 64)]);
+        assert_eq!(
+            BitReader::from(&vec).read_elias_gamma(),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(
+            BitReader::from(&vec).read_exp_golomb(1),
+            Err(DecodeError::Overflow)
+        );
+        let vec = BitVec::from_runs([(false, 64), (true, 1), (true, 64)]);
+        assert_eq!(
+            BitReader::from(&vec).read_exp_golomb(0),
+            Err(DecodeError::Overflow)
+        );
+        let vec = BitVec::from_runs([(true, 7), (false, 15), (true, 1)]);
+        assert_eq!(
+            BitReader::from(&vec).read_elias_omega(),
+            Err(DecodeError::Overflow)
+        );
+        let vec = BitVec::from_runs([(false, 93), (true, 2)]);
+        assert_eq!(
+            BitReader::from(&vec).read_fibonacci(),
+            Err(DecodeError::Overflow)
+        );
+    }
+}